@@ -7,7 +7,7 @@
 //!     cargo run --example wikipedia
 
 use thirtyfour::prelude::*;
-use thirtyfour_query::{ElementPoller, ElementQueryable, ElementWaitable};
+use thirtyfour_query::prelude::*;
 use tokio::time::Duration;
 
 #[tokio::main]
@@ -23,9 +23,7 @@ async fn main() -> color_eyre::Result<()> {
     // Set default ElementPoller strategy. This will be inherited by all future queries unless
     // specifically overridden.
     // The following will wait up to 20 seconds, polling in 0.5 second intervals.
-    let poller =
-        ElementPoller::TimeoutWithInterval(Duration::new(20, 0), Duration::from_millis(1000));
-    driver.config_mut().set("ElementPoller", poller)?;
+    driver.set_default_poller(ElementPoller::timeout_secs(20).interval_ms(500))?;
 
     // Navigate to https://wikipedia.org.
     driver.get("https://wikipedia.org").await?;
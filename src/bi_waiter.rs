@@ -0,0 +1,157 @@
+use crate::observer::notify_observer;
+use crate::poller::{default_poller, session_ticker};
+use crate::{conditions, ElementPairPredicate, ElementPoller, ElementPollerTicker, WaitError};
+use std::time::{Duration, Instant};
+use thirtyfour::error::WebDriverError;
+use thirtyfour::prelude::WebDriverResult;
+use thirtyfour::WebElement;
+
+/// High-level interface for waiting until two elements satisfy one or more conditions
+/// together, such as a tooltip appearing next to the button it describes. Both elements are
+/// passed to each predicate on every poll, so relationships can be checked without capturing
+/// one of the elements in a closure.
+///
+/// The waiter borrows both elements for its lifetime `'a`, so it cannot outlive either of
+/// them, and each predicate receives both references with the lifetime of a single check.
+/// Use `wait_pair()` to create one.
+#[derive(Debug, Clone)]
+pub struct BiElementWaiter<'a> {
+    first: &'a WebElement<'a>,
+    second: &'a WebElement<'a>,
+    poller: ElementPoller,
+    message: String,
+    ignore_errors: bool,
+}
+
+impl<'a> BiElementWaiter<'a> {
+    fn new(first: &'a WebElement<'a>, second: &'a WebElement<'a>, poller: ElementPoller) -> Self {
+        Self {
+            first,
+            second,
+            poller,
+            message: String::new(),
+            ignore_errors: true,
+        }
+    }
+
+    /// Use the specified ElementPoller for this BiElementWaiter.
+    /// This will not affect the default ElementPoller used for other waits.
+    pub fn with_poller(mut self, poller: ElementPoller) -> Self {
+        self.poller = poller;
+        self
+    }
+
+    /// Provide a human-readable error message to be returned in the case of timeout.
+    pub fn error(mut self, message: &str) -> Self {
+        self.message = message.to_string();
+        self
+    }
+
+    /// By default a waiter will ignore any errors that occur while polling for the desired
+    /// condition(s). However, this behaviour can be modified so that the waiter will return
+    /// early if an error is returned from thirtyfour.
+    pub fn ignore_errors(mut self, ignore: bool) -> Self {
+        self.ignore_errors = ignore;
+        self
+    }
+
+    /// Force this BiElementWaiter to wait for the specified timeout, polling once
+    /// after each interval. This will override the poller for this
+    /// BiElementWaiter only.
+    pub fn wait(self, timeout: Duration, interval: Duration) -> Self {
+        self.with_poller(ElementPoller::TimeoutWithInterval(timeout, interval))
+    }
+
+    async fn run_poller(&self, conditions: Vec<ElementPairPredicate>) -> WebDriverResult<()> {
+        let mut ticker = session_ticker(self.first.session, &self.poller)?;
+        let result = self.poll_conditions(&conditions, &mut ticker).await;
+        let description = if self.message.is_empty() {
+            "BiElementWaiter"
+        } else {
+            &self.message
+        };
+        notify_observer(description, &ticker, result.is_ok());
+        result
+    }
+
+    async fn poll_conditions(
+        &self,
+        conditions: &[ElementPairPredicate],
+        ticker: &mut ElementPollerTicker,
+    ) -> WebDriverResult<()> {
+        loop {
+            let check_start = Instant::now();
+            let mut conditions_met = true;
+            for f in conditions {
+                if !f(self.first, self.second).await? {
+                    conditions_met = false;
+                    break;
+                }
+            }
+
+            if conditions_met {
+                return Ok(());
+            }
+
+            ticker.record_check_duration(check_start.elapsed());
+            if !ticker.tick().await {
+                return Err(self.timeout(ticker));
+            }
+        }
+    }
+
+    fn timeout(&self, ticker: &ElementPollerTicker) -> WebDriverError {
+        WaitError::new(&self.message, ticker.attempts(), ticker.elapsed()).into()
+    }
+
+    /// Wait until the specified predicate returns true for the two elements. The predicate
+    /// receives the first and second element in the order they were passed to `wait_pair()`.
+    pub async fn condition(&self, f: ElementPairPredicate) -> WebDriverResult<()> {
+        self.run_poller(vec![f]).await
+    }
+
+    pub async fn conditions(&self, conditions: Vec<ElementPairPredicate>) -> WebDriverResult<()> {
+        self.run_poller(conditions).await
+    }
+
+    /// Wait until the two elements are within `max_gap` pixels of each other.
+    /// See `conditions::elements_are_adjacent()`.
+    pub async fn are_adjacent(&self, max_gap: f64) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::elements_are_adjacent(max_gap, ignore_errors)).await
+    }
+}
+
+/// Create a BiElementWaiter for waiting on a relationship between the two specified elements,
+/// using the default poller of the first element's session:
+///
+/// ```ignore
+/// thirtyfour_query::wait_pair(&tooltip, &button).are_adjacent(8.0).await?;
+/// ```
+pub fn wait_pair<'a>(first: &'a WebElement<'a>, second: &'a WebElement<'a>) -> BiElementWaiter<'a> {
+    let poller = default_poller(first.session);
+    BiElementWaiter::new(first, second, poller)
+}
+
+#[cfg(test)]
+/// This function checks if the public async methods implement Send. It is not intended to be executed.
+async fn _test_is_send() -> WebDriverResult<()> {
+    use thirtyfour::prelude::*;
+
+    // Helper methods
+    fn is_send_val<T: Send>(_val: &T) {}
+
+    // Pre values
+    let caps = DesiredCapabilities::chrome();
+    let driver = WebDriver::new("http://localhost:4444", &caps).await?;
+    let first = driver.find_element(By::Css("button")).await?;
+    let second = driver.find_element(By::Css(".tooltip")).await?;
+
+    // BiElementWaiter
+    is_send_val(&wait_pair(&first, &second).are_adjacent(8.0));
+    is_send_val(&wait_pair(&first, &second).condition(Box::new(|a, b| {
+        Box::pin(async move { Ok(a.is_displayed().await? && b.is_displayed().await?) })
+    })));
+
+    Ok(())
+}
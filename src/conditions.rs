@@ -1,25 +1,309 @@
-use crate::ElementPredicate;
+#[cfg(feature = "visual")]
+use crate::visual;
+use crate::{
+    DriverPredicate, ElementPairPredicate, ElementPredicate, ElementStateSnapshot, SelectorBy,
+};
+use futures::Future;
+use serde_json::Value;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "visual")]
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
 use stringmatch::Needle;
-use thirtyfour::error::WebDriverResult;
+use thirtyfour::error::{WebDriverError, WebDriverResult};
+use thirtyfour::{By, ElementRect, ScriptArgs, WebDriverCommands, WebElement};
 
-pub(crate) fn handle_errors(
+/// Policy for how a condition should handle an error returned while checking an element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ErrorPolicy {
+    /// Treat the error as the condition not being met (yet), and retry on the next poll.
+    Ignore,
+    /// Return the error to the caller, aborting the wait.
+    Propagate,
+    /// Treat the error as the condition returning the specified value.
+    TreatAs(bool),
+}
+
+impl From<bool> for ErrorPolicy {
+    fn from(ignore_errors: bool) -> Self {
+        if ignore_errors {
+            ErrorPolicy::Ignore
+        } else {
+            ErrorPolicy::Propagate
+        }
+    }
+}
+
+pub(crate) fn handle_errors<P>(result: WebDriverResult<bool>, policy: P) -> WebDriverResult<bool>
+where
+    P: Into<ErrorPolicy>,
+{
+    match (result, policy.into()) {
+        (Ok(x), _) => Ok(x),
+        (Err(_), ErrorPolicy::Ignore) => Ok(false),
+        (Err(e), ErrorPolicy::Propagate) => Err(e),
+        (Err(_), ErrorPolicy::TreatAs(x)) => Ok(x),
+    }
+}
+
+pub(crate) fn negate<P>(result: WebDriverResult<bool>, policy: P) -> WebDriverResult<bool>
+where
+    P: Into<ErrorPolicy>,
+{
+    handle_errors(result.map(|x| !x), policy)
+}
+
+/// Handle errors indicating that the element is no longer in the DOM (no such element and
+/// stale element reference) using the `missing` policy, and any other error using `policy`.
+pub(crate) fn handle_missing<P>(
     result: WebDriverResult<bool>,
-    ignore_errors: bool,
-) -> WebDriverResult<bool> {
+    missing: ErrorPolicy,
+    policy: P,
+) -> WebDriverResult<bool>
+where
+    P: Into<ErrorPolicy>,
+{
     match result {
-        Ok(x) => Ok(x),
-        Err(e) => {
-            if ignore_errors {
-                Ok(false)
-            } else {
-                Err(e)
+        Err(e @ WebDriverError::NoSuchElement(_))
+        | Err(e @ WebDriverError::StaleElementReference(_)) => handle_errors(Err(e), missing),
+        x => handle_errors(x, policy),
+    }
+}
+
+/// Execute the specified script with the element passed in as `arguments[0]`, and return
+/// the value returned by the script.
+pub(crate) async fn element_script_value(
+    elem: &WebElement<'_>,
+    script: &str,
+) -> WebDriverResult<Value> {
+    let mut args = ScriptArgs::new();
+    args.push(elem.clone())?;
+    let ret = elem.session.execute_script_with_args(script, &args).await?;
+    Ok(ret.value().clone())
+}
+
+/// Execute the specified script with the element passed in as `arguments[0]`, and return
+/// the string returned by the script, if any.
+pub(crate) async fn element_script_string(
+    elem: &WebElement<'_>,
+    script: &str,
+) -> WebDriverResult<Option<String>> {
+    match element_script_value(elem, script).await? {
+        Value::String(x) => Ok(Some(x)),
+        _ => Ok(None),
+    }
+}
+
+/// Execute the specified script with the element passed in as `arguments[0]`, and return
+/// true only if the script returned `true`.
+pub(crate) async fn element_script_bool(
+    elem: &WebElement<'_>,
+    script: &str,
+) -> WebDriverResult<bool> {
+    Ok(element_script_value(elem, script).await? == Value::Bool(true))
+}
+
+/// Execute the specified script with the element passed in as `arguments[0]` and the other
+/// element passed in as `arguments[1]`, and return true only if the script returned `true`.
+pub(crate) async fn element_pair_script_bool(
+    elem: &WebElement<'_>,
+    other: &WebElement<'_>,
+    script: &str,
+) -> WebDriverResult<bool> {
+    Ok(element_pair_script_value(elem, other, script).await? == Value::Bool(true))
+}
+
+/// Execute the specified script with the element passed in as `arguments[0]` and the other
+/// element passed in as `arguments[1]`, and return the value returned by the script.
+pub(crate) async fn element_pair_script_value(
+    elem: &WebElement<'_>,
+    other: &WebElement<'_>,
+    script: &str,
+) -> WebDriverResult<Value> {
+    let mut args = ScriptArgs::new();
+    args.push(elem.clone())?;
+    args.push(other.clone())?;
+    let ret = elem.session.execute_script_with_args(script, &args).await?;
+    Ok(ret.value().clone())
+}
+
+/// Script returning the explicit ARIA role of an element, or the implicit role for common
+/// elements if no role attribute is present.
+const COMPUTED_ROLE_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const explicit = (elem.getAttribute("role") || "").trim().split(/\s+/)[0];
+    if (explicit) {
+        return explicit;
+    }
+    const tag = elem.tagName.toLowerCase();
+    const type = (elem.getAttribute("type") || "text").toLowerCase();
+    switch (tag) {
+        case "a":
+        case "area":
+            return elem.hasAttribute("href") ? "link" : "generic";
+        case "article": return "article";
+        case "aside": return "complementary";
+        case "button": return "button";
+        case "dialog": return "dialog";
+        case "fieldset": return "group";
+        case "footer": return "contentinfo";
+        case "form": return "form";
+        case "h1": case "h2": case "h3": case "h4": case "h5": case "h6": return "heading";
+        case "header": return "banner";
+        case "hr": return "separator";
+        case "img": return elem.getAttribute("alt") === "" ? "presentation" : "img";
+        case "li": return "listitem";
+        case "main": return "main";
+        case "nav": return "navigation";
+        case "ol": case "ul": return "list";
+        case "option": return "option";
+        case "progress": return "progressbar";
+        case "section": return "region";
+        case "select": return elem.multiple || elem.size > 1 ? "listbox" : "combobox";
+        case "table": return "table";
+        case "tbody": case "thead": case "tfoot": return "rowgroup";
+        case "td": return "cell";
+        case "th": return "columnheader";
+        case "tr": return "row";
+        case "textarea": return "textbox";
+        case "input":
+            switch (type) {
+                case "button": case "image": case "reset": case "submit": return "button";
+                case "checkbox": return "checkbox";
+                case "radio": return "radio";
+                case "range": return "slider";
+                case "number": return "spinbutton";
+                case "search": return "searchbox";
+                default: return "textbox";
             }
+        default: return "generic";
+    }
+"#;
+
+/// Script returning the accessible name (label) of an element.
+const COMPUTED_LABEL_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const labelledBy = elem.getAttribute("aria-labelledby");
+    if (labelledBy) {
+        const text = labelledBy.trim().split(/\s+/)
+            .map(id => document.getElementById(id))
+            .filter(x => x)
+            .map(x => x.textContent.trim())
+            .join(" ");
+        if (text) {
+            return text;
         }
     }
+    const ariaLabel = (elem.getAttribute("aria-label") || "").trim();
+    if (ariaLabel) {
+        return ariaLabel;
+    }
+    if (elem.labels && elem.labels.length > 0) {
+        return Array.from(elem.labels).map(x => x.textContent.trim()).join(" ");
+    }
+    const alt = (elem.getAttribute("alt") || "").trim();
+    if (alt) {
+        return alt;
+    }
+    const text = (elem.textContent || "").replace(/\s+/g, " ").trim();
+    if (text) {
+        return text;
+    }
+    return (elem.getAttribute("title") || "").trim();
+"#;
+
+/// Script returning the text of an element's direct text nodes only, excluding the text of any
+/// descendant elements. Whitespace is collapsed and trimmed.
+const OWN_TEXT_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    return Array.from(elem.childNodes)
+        .filter(node => node.nodeType === Node.TEXT_NODE)
+        .map(node => node.textContent)
+        .join("")
+        .replace(/\s+/g, " ")
+        .trim();
+"#;
+
+/// Evaluate the specified predicate against the element exactly once, without any polling.
+/// This is useful for checking the current state of an element using any of the predicates
+/// in this module.
+pub async fn check_once(
+    elem: &WebElement<'_>,
+    predicate: &ElementPredicate,
+) -> WebDriverResult<bool> {
+    predicate(elem).await
+}
+
+/// Async function that checks an element, as accepted by `predicate()`.
+///
+/// This is implemented for any function taking a `&WebElement` and returning a future that
+/// resolves to `WebDriverResult<bool>`, and only exists to allow the returned future to
+/// borrow the element. You should not need to implement it yourself.
+pub trait ElementCheck<'a>: Fn(&'a WebElement<'a>) -> <Self as ElementCheck<'a>>::Fut {
+    type Fut: Future<Output = WebDriverResult<bool>> + Send + 'a;
+}
+
+impl<'a, F, Fut> ElementCheck<'a> for F
+where
+    F: Fn(&'a WebElement<'a>) -> Fut,
+    Fut: Future<Output = WebDriverResult<bool>> + Send + 'a,
+{
+    type Fut = Fut;
+}
+
+/// Async function that extracts a value from an element, as accepted by
+/// `ElementWaiter::wait_for_value()`.
+///
+/// Like `ElementCheck`, this is implemented for any function taking a `&WebElement` and
+/// returning a future, in this case resolving to `WebDriverResult<Option<T>>`. You should not
+/// need to implement it yourself.
+pub trait ElementValueCheck<'a, T>:
+    Fn(&'a WebElement<'a>) -> <Self as ElementValueCheck<'a, T>>::Fut
+{
+    type Fut: Future<Output = WebDriverResult<Option<T>>> + Send + 'a;
+}
+
+impl<'a, T, F, Fut> ElementValueCheck<'a, T> for F
+where
+    F: Fn(&'a WebElement<'a>) -> Fut,
+    Fut: Future<Output = WebDriverResult<Option<T>>> + Send + 'a,
+{
+    type Fut = Fut;
+}
+
+/// Create a predicate from the specified async function, without having to box and pin the
+/// returned future manually.
+///
+/// Due to current limitations of closures in Rust, this works best with an `async fn`:
+/// ```ignore
+/// async fn has_items(elem: &WebElement<'_>) -> WebDriverResult<bool> {
+///     Ok(!elem.find_elements(By::Tag("li")).await?.is_empty())
+/// }
+///
+/// elem.wait_until().condition(conditions::predicate(has_items)).await?;
+/// ```
+///
+/// For predicates that need to capture state, build the `ElementPredicate` manually using
+/// `Box::new(move |elem| Box::pin(async move { ... }))` instead.
+pub fn predicate<F>(f: F) -> ElementPredicate
+where
+    F: for<'a> ElementCheck<'a> + Send + Sync + 'static,
+{
+    Box::new(move |elem| Box::pin(f(elem)))
 }
 
-pub(crate) fn negate(result: WebDriverResult<bool>, ignore_errors: bool) -> WebDriverResult<bool> {
-    handle_errors(result.map(|x| !x), ignore_errors)
+/// Predicate that ignores the element and returns the result of the specified async function.
+/// This allows polling external state (e.g. a database or HTTP check) using the same timeout
+/// machinery, optionally combined with element conditions via `ElementWaiter::conditions()`.
+pub fn external_condition<F, Fut>(f: F) -> ElementPredicate
+where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = WebDriverResult<bool>> + Send + 'static,
+{
+    Box::new(move |_| Box::pin(f()))
 }
 
 /// Predicate that returns true for elements that are enabled.
@@ -34,6 +318,64 @@ pub fn element_is_not_enabled(ignore_errors: bool) -> ElementPredicate {
     Box::new(move |elem| Box::pin(async move { negate(elem.is_enabled().await, ignore_errors) }))
 }
 
+/// Predicate that returns true for elements whose `disabled` DOM property is true.
+///
+/// There are three common signals for an element being disabled:
+/// - `element_is_not_enabled()` uses the WebDriver "Is Element Enabled" command, which is the
+///   best choice for native form controls such as `<button disabled>`.
+/// - `element_property_disabled()` checks the `disabled` DOM property, which is useful for
+///   custom elements that implement the property without being native form controls.
+/// - `element_is_aria_disabled()` checks the `aria-disabled="true"` attribute, which is how
+///   many design-system components signal that they are disabled.
+pub fn element_property_disabled(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(elem, "return arguments[0].disabled === true;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the attribute `aria-disabled="true"`.
+/// See `element_property_disabled()` for the different ways of checking if an element is
+/// disabled.
+pub fn element_is_aria_disabled(ignore_errors: bool) -> ElementPredicate {
+    element_has_attribute("aria-disabled", "true", ignore_errors)
+}
+
+/// Predicate that returns true for elements whose `checked` DOM property is true, e.g.
+/// checkboxes and radio buttons.
+///
+/// This reflects the live property, which is toggled by user interaction and by scripts,
+/// rather than the `checked` HTML attribute, which only sets the initial state and is not
+/// updated when the element is checked or unchecked. For custom components that expose their
+/// state via `aria-checked` instead, use `element_has_attribute("aria-checked", "true", ..)`.
+pub fn element_property_checked(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(elem, "return arguments[0].checked === true;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose `checked` DOM property is not true.
+/// See `element_property_checked()` for more details.
+pub fn element_property_not_checked(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            negate(
+                element_script_bool(elem, "return arguments[0].checked === true;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
 /// Predicate that returns true for elements that are selected.
 pub fn element_is_selected(ignore_errors: bool) -> ElementPredicate {
     Box::new(move |elem| {
@@ -70,6 +412,110 @@ pub fn element_is_not_clickable(ignore_errors: bool) -> ElementPredicate {
     Box::new(move |elem| Box::pin(async move { negate(elem.is_clickable().await, ignore_errors) }))
 }
 
+/// Predicate that returns true for elements that have been laid out with a non-zero width and
+/// height, e.g. as a precondition for taking a screenshot of the element.
+///
+/// This is lighter-weight than `element_is_displayed()`, as it only checks the element's rect.
+/// An element with a non-zero size may still be invisible, e.g. due to `visibility: hidden`,
+/// `opacity: 0` or being scrolled out of view, and an element with zero size may still have
+/// visible overflowing content.
+pub fn element_has_nonzero_size(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(elem.rect().await.map(|r| r.width > 0.0 && r.height > 0.0), ignore_errors)
+        })
+    })
+}
+
+/// Script returning true if the `value` property of the element differs from its
+/// `defaultValue` property. Elements without a `defaultValue` are never dirty.
+const DIRTY_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    return elem.defaultValue !== undefined && elem.value !== elem.defaultValue;
+"#;
+
+/// Predicate that returns true for form fields that have been modified, i.e. whose `value`
+/// property differs from their `defaultValue` property (the initial value from the `value`
+/// attribute, or the initial content for a `textarea`).
+///
+/// This applies to text-like `input` elements and `textarea` elements. Elements without a
+/// `defaultValue` property, such as `select` elements, are never considered dirty, and for
+/// checkboxes and radio buttons the checked state is not compared, so use
+/// `element_property_checked()` for those. Note that a field edited and then changed back
+/// to its initial value is pristine again.
+pub fn element_is_dirty(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(element_script_bool(elem, DIRTY_SCRIPT).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for form fields that have not been modified, e.g. after a form
+/// reset. See `element_is_dirty()` for more details.
+pub fn element_is_pristine(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(
+            async move { negate(element_script_bool(elem, DIRTY_SCRIPT).await, ignore_errors) },
+        )
+    })
+}
+
+/// Predicate that returns true for elements whose `offsetParent` is not null, i.e. that are
+/// rendered in the layout. This is a cheap way to detect a `display: none` on the element or
+/// any of its ancestors, using a single script rather than a full visibility check.
+///
+/// NOTE: `offsetParent` is also null for elements with `position: fixed` (and for the `body`
+/// and `html` elements), even when they are visible, so this should not be used for fixed
+/// elements such as sticky headers or overlays. It also does not account for `visibility:
+/// hidden` or elements collapsed to zero size. Combine it with `element_has_nonzero_size()`
+/// to also require that the element occupies space.
+pub fn element_has_offset_parent(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(elem, "return arguments[0].offsetParent !== null;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose handle can no longer be used, i.e.
+/// interacting with the element returns a stale element reference error.
+///
+/// This differs subtly from `element_is_stale()`, which also returns true for a no such
+/// element error. This predicate only returns true for a stale element reference error
+/// specifically, and any other error is handled according to `ignore_errors`.
+pub fn element_is_invalid(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            match elem.tag_name().await {
+                Ok(_) => Ok(false),
+                Err(WebDriverError::StaleElementReference(_)) => Ok(true),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Predicate that returns true for elements that are no longer present in the DOM.
+///
+/// A no such element or stale element reference error means the element is gone, so it is
+/// treated as success rather than an error. Any other error is handled according to
+/// `ignore_errors`.
+pub fn element_is_stale(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_missing(
+                elem.tag_name().await.map(|_| false),
+                ErrorPolicy::TreatAs(true),
+                ignore_errors,
+            )
+        })
+    })
+}
+
 /// Predicate that returns true for elements that have the specified class name.
 /// See the `Needle` documentation for more details on text matching rules.
 /// In particular, it is recommended to use StringMatch or Regex to perform a whole-word search.
@@ -108,6 +554,34 @@ where
     })
 }
 
+/// Split the specified `class` attribute value into its set of class names.
+fn class_set(classes: &str) -> HashSet<&str> {
+    classes.split_whitespace().collect()
+}
+
+/// Predicate that returns true for elements whose set of class names is exactly the specified
+/// set, i.e. the element has all of the specified classes and no others.
+///
+/// The `class` attribute is split on any whitespace, so extra spaces, tabs and newlines are
+/// ignored, and the classes are compared as an unordered set, so neither the order nor any
+/// duplicates (in the attribute or in `class_names`) matter. An element without a `class`
+/// attribute only matches an empty set.
+pub fn element_classes_equal(class_names: &[&str], ignore_errors: bool) -> ElementPredicate {
+    let class_names: Vec<String> = class_names.iter().map(|x| x.to_string()).collect();
+    Box::new(move |elem| {
+        let class_names = class_names.clone();
+        Box::pin(async move {
+            match elem.class_name().await {
+                Ok(x) => {
+                    let expected: HashSet<&str> = class_names.iter().map(|x| x.as_str()).collect();
+                    Ok(class_set(x.as_deref().unwrap_or_default()) == expected)
+                }
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
 /// Predicate that returns true for elements that have the specified text.
 /// See the `Needle` documentation for more details on text matching rules.
 pub fn element_has_text<N>(text: N, ignore_errors: bool) -> ElementPredicate
@@ -122,29 +596,54 @@ where
     })
 }
 
-/// Predicate that returns true for elements that do not contain the specified text.
-/// See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_text<N>(text: N, ignore_errors: bool) -> ElementPredicate
+/// Collapse each run of whitespace in the specified text into a single space, and remove any
+/// leading and trailing whitespace.
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+/// Predicate that returns true for elements whose text matches the specified text after
+/// normalizing whitespace. See the `Needle` documentation for more details on text matching
+/// rules.
+///
+/// Before matching, every run of whitespace is collapsed into a single space and leading and
+/// trailing whitespace is removed. Whitespace is any character with the Unicode `White_Space`
+/// property (see `char::is_whitespace()`), which includes spaces, tabs and newlines as well
+/// as non-breaking spaces. Zero-width characters are not whitespace and are left unchanged.
+pub fn element_normalized_text_is<N>(text: N, ignore_errors: bool) -> ElementPredicate
 where
     N: Needle + Clone + Send + Sync + 'static,
 {
     Box::new(move |elem| {
         let text = text.clone();
-        Box::pin(async move { negate(elem.text().await.map(|x| text.is_match(&x)), ignore_errors) })
+        Box::pin(async move {
+            handle_errors(
+                elem.text().await.map(|x| text.is_match(&normalize_whitespace(&x))),
+                ignore_errors,
+            )
+        })
     })
 }
 
-/// Predicate that returns true for elements that have the specified value.
+/// Predicate that returns true for elements whose `innerHTML` property matches the specified
+/// needle, e.g. to check that an icon was injected:
+/// `element_inner_html_matches(StringMatch::new("<svg").partial(), true)`.
 /// See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_value<N>(value: N, ignore_errors: bool) -> ElementPredicate
+///
+/// NOTE: The markup is serialized by the browser, so attribute order, quoting and whitespace
+/// may differ from the source, and frameworks often add comments, generated ids and
+/// indentation that change between renders. Exact matches are therefore brittle; prefer a
+/// partial match on a distinctive fragment (or a regex). If the assertion is really about the
+/// rendered text, use `element_normalized_text_is()` instead.
+pub fn element_inner_html_matches<N>(needle: N, ignore_errors: bool) -> ElementPredicate
 where
     N: Needle + Clone + Send + Sync + 'static,
 {
     Box::new(move |elem| {
-        let value = value.clone();
+        let needle = needle.clone();
         Box::pin(async move {
-            match elem.value().await {
-                Ok(Some(x)) => Ok(value.is_match(&x)),
+            match elem.get_property("innerHTML").await {
+                Ok(Some(x)) => Ok(needle.is_match(&x)),
                 Ok(None) => Ok(false),
                 Err(e) => handle_errors(Err(e), ignore_errors),
             }
@@ -152,153 +651,132 @@ where
     })
 }
 
-/// Predicate that returns true for elements that do not contain the specified value.
+/// Predicate that returns true for elements that do not contain the specified text.
 /// See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_value<N>(value: N, ignore_errors: bool) -> ElementPredicate
+pub fn element_lacks_text<N>(text: N, ignore_errors: bool) -> ElementPredicate
 where
     N: Needle + Clone + Send + Sync + 'static,
 {
     Box::new(move |elem| {
-        let value = value.clone();
-        Box::pin(async move {
-            match elem.value().await {
-                Ok(Some(x)) => Ok(!value.is_match(&x)),
-                Ok(None) => Ok(true),
-                Err(e) => handle_errors(Err(e), ignore_errors),
-            }
-        })
+        let text = text.clone();
+        Box::pin(async move { negate(elem.text().await.map(|x| text.is_match(&x)), ignore_errors) })
     })
 }
 
-/// Predicate that returns true for elements that have the specified attribute with the specified
-/// value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_attribute<S, N>(
-    attribute_name: S,
-    value: N,
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose text matches any of the specified needles.
+/// The text is read once per evaluation, and the needles are then checked in order, stopping
+/// at the first match.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_text_contains_any<N>(texts: &[N], ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String>,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let attribute_name: String = attribute_name.into();
+    let texts = texts.to_vec();
     Box::new(move |elem| {
-        let attribute_name: String = attribute_name.clone();
-        let value = value.clone();
+        let texts = texts.clone();
         Box::pin(async move {
-            match elem.get_attribute(&attribute_name).await {
-                Ok(Some(x)) => Ok(value.is_match(&x)),
-                Ok(None) => Ok(false),
-                Err(e) => handle_errors(Err(e), ignore_errors),
-            }
+            handle_errors(
+                elem.text().await.map(|x| texts.iter().any(|text| text.is_match(&x))),
+                ignore_errors,
+            )
         })
     })
 }
 
-/// Predicate that returns true for elements that lack the specified attribute with the
-/// specified value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_attribute<S, N>(
-    attribute_name: S,
-    value: N,
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose text is one of an enumerated set, e.g.
+/// `element_text_in(&["Ready", "Done", "Complete"], true)` for a status label.
+/// See the `Needle` documentation for more details on text matching rules, i.e. a `&str`
+/// needle must match the whole text.
+///
+/// The text is read once per evaluation, and the needles are then checked in the order
+/// given, stopping at the first match, so put the cheapest or most likely needles first.
+pub fn element_text_in<N>(texts: &[N], ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String>,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let attribute_name: String = attribute_name.into();
+    let texts = texts.to_vec();
     Box::new(move |elem| {
-        let attribute_name: String = attribute_name.clone();
-        let value = value.clone();
+        let texts = texts.clone();
         Box::pin(async move {
-            match elem.get_attribute(&attribute_name).await {
-                Ok(Some(x)) => Ok(!value.is_match(&x)),
-                Ok(None) => Ok(true),
-                Err(e) => handle_errors(Err(e), ignore_errors),
-            }
+            handle_errors(
+                elem.text().await.map(|x| texts.iter().any(|text| text.is_match(&x))),
+                ignore_errors,
+            )
         })
     })
 }
 
-/// Predicate that returns true for elements that have all of the specified attributes with the
-/// specified values. See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_attributes<S, N>(
-    desired_attributes: &[(S, N)],
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose text matches all of the specified needles.
+/// The text is read once per evaluation.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_text_contains_all<N>(texts: &[N], ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String> + Clone,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let desired_attributes: Vec<(String, N)> =
-        desired_attributes.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    let texts = texts.to_vec();
     Box::new(move |elem| {
-        let desired_attributes = desired_attributes.clone();
+        let texts = texts.clone();
         Box::pin(async move {
-            for (attribute_name, value) in &desired_attributes {
-                match elem.get_attribute(&attribute_name).await {
-                    Ok(Some(x)) => {
-                        if !value.is_match(&x) {
-                            return Ok(false);
-                        }
-                    }
-                    Ok(None) => return Ok(false),
-                    Err(e) => return handle_errors(Err(e), ignore_errors),
-                }
-            }
-            Ok(true)
+            handle_errors(
+                elem.text().await.map(|x| texts.iter().all(|text| text.is_match(&x))),
+                ignore_errors,
+            )
         })
     })
 }
 
-/// Predicate that returns true for elements that do not have any of the specified attributes with
-/// the specified values. See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_attributes<S, N>(
-    desired_attributes: &[(S, N)],
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose own text matches the specified text.
+/// See the `Needle` documentation for more details on text matching rules.
+///
+/// Unlike `element_has_text()`, this only includes the element's direct text nodes (obtained
+/// by iterating the element's `childNodes` via JavaScript), so the text of any child elements is
+/// excluded. For example, the own text of `<div>Label <span>99</span></div>` is "Label".
+/// Runs of whitespace are collapsed into a single space, and leading/trailing whitespace is
+/// removed. Note that this is the text in the DOM, not the rendered text, so text hidden via
+/// CSS is included.
+pub fn element_has_own_text<N>(text: N, ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String> + Clone,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let desired_attributes: Vec<(String, N)> =
-        desired_attributes.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
     Box::new(move |elem| {
-        let desired_attributes = desired_attributes.clone();
+        let text = text.clone();
         Box::pin(async move {
-            for (attribute_name, value) in &desired_attributes {
-                match elem.get_attribute(&attribute_name).await {
-                    Ok(Some(x)) => {
-                        if value.is_match(&x) {
-                            return Ok(false);
-                        }
-                    }
-                    Ok(None) => {}
-                    Err(e) => return handle_errors(Err(e), ignore_errors),
-                }
+            match element_script_string(elem, OWN_TEXT_SCRIPT).await {
+                Ok(Some(x)) => Ok(text.is_match(&x)),
+                Ok(None) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
             }
-            Ok(true)
         })
     })
 }
 
-/// Predicate that returns true for elements that have the specified property with the specified
-/// value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_property<S, N>(
-    property_name: S,
-    value: N,
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose text length is between `min` and `max`
+/// characters (inclusive). The length is measured in characters (not bytes) of the rendered
+/// text, as returned by `WebElement::text()`.
+pub fn element_text_len_between(min: usize, max: usize, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                elem.text().await.map(|x| {
+                    let len = x.chars().count();
+                    (min..=max).contains(&len)
+                }),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the specified value.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_value<N>(value: N, ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String>,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let property_name: String = property_name.into();
     Box::new(move |elem| {
-        let property_name = property_name.clone();
         let value = value.clone();
         Box::pin(async move {
-            match elem.get_property(&property_name).await {
+            match elem.value().await {
                 Ok(Some(x)) => Ok(value.is_match(&x)),
                 Ok(None) => Ok(false),
                 Err(e) => handle_errors(Err(e), ignore_errors),
@@ -307,97 +785,341 @@ where
     })
 }
 
-/// Predicate that returns true for elements that lack the specified property with the
-/// specified value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_property<S, N>(
-    property_name: S,
-    value: N,
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose `value` property is a number between `min`
+/// and `max` (inclusive), e.g. for range inputs and number fields.
+///
+/// If the value is missing or cannot be parsed as a number, this returns an error, which is
+/// treated as not matching if `ignore_errors` is true.
+pub fn element_number_value_between(min: f64, max: f64, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            let result = match elem.get_property("value").await {
+                Ok(x) => match x.as_deref().map(|v| v.trim().parse::<f64>()) {
+                    Some(Ok(value)) => Ok((min..=max).contains(&value)),
+                    _ => Err(WebDriverError::CustomError(format!(
+                        "Element value is not a number: {:?}",
+                        x
+                    ))),
+                },
+                Err(e) => Err(e),
+            };
+            handle_errors(result, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose text is successfully parsed by the
+/// specified parser, i.e. the parser returns `Some`. This is useful for waiting until an
+/// element contains well-formed data before reading it.
+///
+/// A parse failure (i.e. the parser returns `None`) is treated as not matching yet, rather than
+/// as an error, so polling continues until the text can be parsed or the poller times out.
+pub fn element_text_parses<F, T>(parser: F, ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String>,
-    N: Needle + Clone + Send + Sync + 'static,
+    F: Fn(&str) -> Option<T> + Send + Sync + 'static,
 {
-    let property_name: String = property_name.into();
+    let parser = Arc::new(parser);
     Box::new(move |elem| {
-        let property_name = property_name.clone();
-        let value = value.clone();
+        let parser = parser.clone();
         Box::pin(async move {
-            match elem.get_property(&property_name).await {
-                Ok(Some(x)) => Ok(!value.is_match(&x)),
-                Ok(None) => Ok(true),
-                Err(e) => handle_errors(Err(e), ignore_errors),
-            }
+            handle_errors(elem.text().await.map(|x| parser(&x).is_some()), ignore_errors)
         })
     })
 }
 
-/// Predicate that returns true for elements that have all of the specified properties with the
-/// specified value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_properties<S, N>(
-    desired_properties: &[(S, N)],
+/// Predicate that returns true for elements whose text is a timestamp within `window` of the
+/// current time, e.g. for a "last updated" field that should show a recent time. The text is
+/// converted to a `SystemTime` by the specified parser, e.g. using the `chrono` crate:
+///
+/// ```ignore
+/// let parser = |text: &str| {
+///     let time = chrono::DateTime::parse_from_rfc3339(text).ok()?;
+///     Some(SystemTime::from(time))
+/// };
+/// elem.wait_until().timestamp_within(Duration::from_secs(60), parser).await?;
+/// ```
+///
+/// The parser is responsible for timezones: `SystemTime` is an absolute point in time, so a
+/// timestamp rendered in local time without an offset must be interpreted in the timezone
+/// used by the page, which is not necessarily that of the machine running the test. The
+/// comparison is against the clock of the machine running the test, and timestamps in the
+/// future are accepted if they are within `window` too, to allow for clock skew between the
+/// browser (or server) and the test machine.
+///
+/// A parse failure (i.e. the parser returns `None`) is treated as not matching, rather than
+/// as an error, so polling continues until the text is a recent timestamp.
+pub fn element_text_timestamp_within<F>(
+    window: Duration,
+    parser: F,
     ignore_errors: bool,
 ) -> ElementPredicate
 where
-    S: Into<String> + Clone,
-    N: Needle + Clone + Send + Sync + 'static,
+    F: Fn(&str) -> Option<SystemTime> + Send + Sync + 'static,
 {
-    let desired_properties: Vec<(String, N)> =
-        desired_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    let parser = Arc::new(parser);
     Box::new(move |elem| {
-        let desired_properties = desired_properties.clone();
+        let parser = parser.clone();
         Box::pin(async move {
-            for (property_name, value) in &desired_properties {
-                match elem.get_property(property_name).await {
-                    Ok(Some(x)) => {
-                        if !value.is_match(&x) {
-                            return Ok(false);
-                        }
-                    }
-                    Ok(None) => return Ok(false),
-                    Err(e) => return handle_errors(Err(e), ignore_errors),
+            handle_errors(
+                elem.text().await.map(|x| match parser(&x) {
+                    Some(time) => is_within(time, SystemTime::now(), window),
+                    None => false,
+                }),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Return true if the specified times are no more than `window` apart, in either direction.
+fn is_within(a: SystemTime, b: SystemTime, window: Duration) -> bool {
+    match a.duration_since(b) {
+        Ok(x) => x <= window,
+        Err(e) => e.duration() <= window,
+    }
+}
+
+/// Predicate that returns true for elements whose text is valid JSON.
+/// See `element_text_parses()` for more details.
+pub fn element_text_is_valid_json(ignore_errors: bool) -> ElementPredicate {
+    element_text_parses(|x| serde_json::from_str::<Value>(x).ok(), ignore_errors)
+}
+
+/// Return true if the specified text is an RFC 3339 date and time,
+/// e.g. `2021-03-04T05:06:07Z` or `2021-03-04T05:06:07.123+01:00`.
+fn is_rfc3339(text: &str) -> bool {
+    fn digits(s: &str, min: u32, max: u32) -> bool {
+        s.chars().all(|c| c.is_ascii_digit())
+            && s.parse::<u32>().is_ok_and(|x| (min..=max).contains(&x))
+    }
+
+    let text = text.trim();
+    if !text.is_ascii() || text.len() < 20 {
+        return false;
+    }
+    let (date, rest) = text.split_at(10);
+    let (time, rest) = rest.split_at(9);
+    let is_date = date.as_bytes()[4] == b'-'
+        && date.as_bytes()[7] == b'-'
+        && digits(&date[0..4], 0, 9999)
+        && digits(&date[5..7], 1, 12)
+        && digits(&date[8..10], 1, 31);
+    let is_time = matches!(time.as_bytes()[0], b'T' | b't' | b' ')
+        && time.as_bytes()[3] == b':'
+        && time.as_bytes()[6] == b':'
+        && digits(&time[1..3], 0, 23)
+        && digits(&time[4..6], 0, 59)
+        && digits(&time[7..9], 0, 60);
+
+    // Optional fractional seconds, followed by the offset.
+    let offset = match rest.strip_prefix('.') {
+        Some(x) => {
+            let frac_len = x.chars().take_while(|c| c.is_ascii_digit()).count();
+            if frac_len == 0 {
+                return false;
+            }
+            &x[frac_len..]
+        }
+        None => rest,
+    };
+    let is_offset = match offset {
+        "Z" | "z" => true,
+        x if x.len() == 6 && (x.starts_with('+') || x.starts_with('-')) => {
+            x.as_bytes()[3] == b':' && digits(&x[1..3], 0, 23) && digits(&x[4..6], 0, 59)
+        }
+        _ => false,
+    };
+
+    is_date && is_time && is_offset
+}
+
+/// Predicate that returns true for elements whose text is an RFC 3339 date and time, e.g.
+/// `2021-03-04T05:06:07Z`. The format is validated, but not whether the day exists in the
+/// given month. See `element_text_parses()` for more details.
+pub fn element_text_is_valid_rfc3339_date(ignore_errors: bool) -> ElementPredicate {
+    element_text_parses(
+        |x| {
+            if is_rfc3339(x) {
+                Some(())
+            } else {
+                None
+            }
+        },
+        ignore_errors,
+    )
+}
+
+/// Parse the first number in the specified text, e.g. "Progress: 45%" is parsed as 45.
+///
+/// The number may have a minus sign directly in front of it, commas grouping its integer
+/// digits in threes, and a decimal point followed by digits. Parsing stops at the first
+/// character that does not fit, so "Step 3 of 10" is parsed as 3 and "v1.2.3" as 1.2.
+fn parse_number(text: &str) -> Option<f64> {
+    let chars: Vec<char> = text.chars().collect();
+    let is_digit = |i: usize| chars.get(i).is_some_and(|c| c.is_ascii_digit());
+    let start = (0..chars.len()).find(|&i| is_digit(i))?;
+
+    let mut number = String::new();
+    if start > 0 && chars[start - 1] == '-' {
+        number.push('-');
+    }
+    let mut has_point = false;
+    let mut i = start;
+    while i < chars.len() {
+        let c = chars[i];
+        let is_grouping = c == ',' && (1..=3).all(|n| is_digit(i + n)) && !is_digit(i + 4);
+        if c.is_ascii_digit() {
+            number.push(c);
+        } else if c == '.' && !has_point && is_digit(i + 1) {
+            has_point = true;
+            number.push(c);
+        } else if has_point || !is_grouping {
+            break;
+        }
+        i += 1;
+    }
+    number.parse().ok()
+}
+
+/// Read the element's text and parse it as a number. Returns an error if the text does not
+/// contain a number.
+async fn element_text_number(elem: &WebElement<'_>) -> WebDriverResult<f64> {
+    let text = elem.text().await?;
+    parse_number(&text).ok_or_else(|| {
+        WebDriverError::CustomError(format!("Element text is not a number: {:?}", text))
+    })
+}
+
+/// Predicate that returns true for elements whose text is a number greater than or equal to
+/// `target`, e.g. for progress counters.
+///
+/// The first number in the text is used, so "45%" and "1,234 of 2,000 items" are parsed as
+/// 45 and 1234 respectively.
+/// If the text does not contain a number, this returns an error, which is treated as not
+/// matching if `ignore_errors` is true.
+pub fn element_number_reaches(target: f64, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(element_text_number(elem).await.map(|x| x >= target), ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose text is a number greater than or equal to
+/// `target`, and which never decreased while polling. The text is parsed as for
+/// `element_number_reaches()`.
+///
+/// The last parsed value is tracked between calls, so a new predicate should be created for
+/// each wait. If the value ever decreases, this fails fast by returning an error rather than
+/// retrying, regardless of `ignore_errors`. Other errors are handled according to
+/// `ignore_errors`.
+pub fn element_number_reaches_monotonic(target: f64, ignore_errors: bool) -> ElementPredicate {
+    let last_value: Arc<Mutex<Option<f64>>> = Arc::new(Mutex::new(None));
+    Box::new(move |elem| {
+        let last_value = last_value.clone();
+        Box::pin(async move {
+            let value = match element_text_number(elem).await {
+                Ok(x) => x,
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            };
+
+            let mut last_value = last_value.lock().unwrap();
+            if let Some(last) = *last_value {
+                if value < last {
+                    return Err(WebDriverError::CustomError(format!(
+                        "Element number decreased from {} to {}",
+                        last, value
+                    )));
                 }
             }
-            Ok(true)
+            *last_value = Some(value);
+            Ok(value >= target)
         })
     })
 }
 
-/// Predicate that returns true for elements that do not have any of the specified properties with
-/// the specified values. See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_properties<S, N>(
-    desired_properties: &[(S, N)],
+/// Read the specified attribute and parse it as a number. Returns an error if the attribute
+/// is missing or is not a number.
+async fn element_attr_number(elem: &WebElement<'_>, name: &str) -> WebDriverResult<f64> {
+    let value = elem.get_attribute(name).await?;
+    value.as_deref().and_then(|x| x.trim().parse::<f64>().ok()).ok_or_else(|| {
+        WebDriverError::CustomError(format!("Attribute {} is not a number: {:?}", name, value))
+    })
+}
+
+/// Predicate that returns true for elements whose specified attribute is a number greater
+/// than or equal to `target`, e.g. `element_attr_number_reaches("aria-valuenow", 100.0, true)`
+/// for a progress bar.
+///
+/// The attribute must be a plain number (surrounding whitespace is ignored). If it is
+/// missing or cannot be parsed, this returns an error, which is treated as not matching if
+/// `ignore_errors` is true. See `element_attr_number_at_least()` for a variant that never
+/// returns an error for this.
+///
+/// To wait for a progress bar to complete regardless of its range, read `aria-valuemax`
+/// first and use it as the target for `aria-valuenow`:
+///
+/// ```ignore
+/// let max = elem.get_attribute("aria-valuemax").await?.and_then(|x| x.parse().ok());
+/// let predicate = element_attr_number_reaches("aria-valuenow", max.unwrap_or(100.0), true);
+/// ```
+pub fn element_attr_number_reaches(
+    name: &str,
+    target: f64,
     ignore_errors: bool,
-) -> ElementPredicate
+) -> ElementPredicate {
+    let name = name.to_string();
+    Box::new(move |elem| {
+        let name = name.clone();
+        Box::pin(async move {
+            handle_errors(
+                element_attr_number(elem, &name).await.map(|x| x >= target),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose specified attribute is a number greater
+/// than or equal to `min`. This is the same as `element_attr_number_reaches()`, except that a
+/// missing attribute or a value that cannot be parsed as a number is treated as not matching
+/// rather than as an error, e.g. for a progress bar that only sets `aria-valuenow` once the
+/// progress is known. Other errors are handled according to `ignore_errors`.
+pub fn element_attr_number_at_least(name: &str, min: f64, ignore_errors: bool) -> ElementPredicate {
+    let name = name.to_string();
+    Box::new(move |elem| {
+        let name = name.clone();
+        Box::pin(async move {
+            match elem.get_attribute(&name).await {
+                Ok(x) => Ok(x.and_then(|x| x.trim().parse::<f64>().ok()).is_some_and(|x| x >= min)),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Predicate that returns true for elements that do not contain the specified value.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_value<N>(value: N, ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String> + Clone,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let desired_properties: Vec<(String, N)> =
-        desired_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
     Box::new(move |elem| {
-        let desired_properties = desired_properties.clone();
+        let value = value.clone();
         Box::pin(async move {
-            for (property_name, value) in &desired_properties {
-                match elem.get_property(property_name).await {
-                    Ok(Some(x)) => {
-                        if value.is_match(&x) {
-                            return Ok(false);
-                        }
-                    }
-                    Ok(None) => {}
-                    Err(e) => return handle_errors(Err(e), ignore_errors),
-                }
+            match elem.value().await {
+                Ok(Some(x)) => Ok(!value.is_match(&x)),
+                Ok(None) => Ok(true),
+                Err(e) => handle_errors(Err(e), ignore_errors),
             }
-            Ok(true)
         })
     })
 }
 
-/// Predicate that returns true for elements that have the specified CSS property with the specified
+/// Predicate that returns true for elements that have the specified attribute with the specified
 /// value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_css_property<S, N>(
-    css_property_name: S,
+pub fn element_has_attribute<S, N>(
+    attribute_name: S,
     value: N,
     ignore_errors: bool,
 ) -> ElementPredicate
@@ -405,101 +1127,2320 @@ where
     S: Into<String>,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let css_property_name: String = css_property_name.into();
+    let attribute_name: String = attribute_name.into();
     Box::new(move |elem| {
-        let css_property_name = css_property_name.clone();
+        let attribute_name: String = attribute_name.clone();
         let value = value.clone();
         Box::pin(async move {
-            handle_errors(
-                elem.get_css_property(&css_property_name).await.map(|x| value.is_match(&x)),
-                ignore_errors,
-            )
+            match elem.get_attribute(&attribute_name).await {
+                Ok(Some(x)) => Ok(value.is_match(&x)),
+                Ok(None) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
         })
     })
 }
 
-/// Predicate that returns true for elements that lack the specified CSS property with the
-/// specified value. See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_css_property<S, N>(
-    css_property_name: S,
-    value: N,
-    ignore_errors: bool,
-) -> ElementPredicate
+/// Predicate that returns true for elements whose `data-*` attribute with the specified name
+/// is exactly equal to the specified value, e.g. `element_data_attr_is("state", "ready", true)`
+/// for `data-state="ready"`.
+///
+/// The `data-` prefix is added to the name unless it is already present, so `"state"` and
+/// `"data-state"` are equivalent. Use `element_has_attribute()` for other kinds of matching.
+pub fn element_data_attr_is(name: &str, value: &str, ignore_errors: bool) -> ElementPredicate {
+    let attribute_name = if name.starts_with("data-") {
+        name.to_string()
+    } else {
+        format!("data-{}", name)
+    };
+    element_has_attribute(attribute_name, value.to_string(), ignore_errors)
+}
+
+/// Predicate that returns true for elements whose inline `style` attribute contains a
+/// declaration matching the specified value, e.g. `"opacity: 0"` or
+/// `StringMatch::new("translateX").partial()`.
+/// See the `Needle` documentation for more details on text matching rules.
+///
+/// The attribute is split into declarations on `;`, and each declaration is trimmed before
+/// matching, so the value does not depend on the other declarations or their order.
+///
+/// NOTE: This only checks the raw inline `style` attribute as written in the DOM, not the
+/// computed style. Styles applied by stylesheets or inherited from ancestors are not seen,
+/// and values are not normalized by the browser. Use `element_has_css_property()` to check
+/// the computed value of a CSS property instead.
+pub fn element_style_contains<N>(value: N, ignore_errors: bool) -> ElementPredicate
 where
-    S: Into<String>,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let css_property_name: String = css_property_name.into();
     Box::new(move |elem| {
-        let css_property_name = css_property_name.clone();
         let value = value.clone();
         Box::pin(async move {
-            handle_errors(
-                elem.get_css_property(&css_property_name).await.map(|x| !value.is_match(&x)),
-                ignore_errors,
-            )
+            match elem.get_attribute("style").await {
+                Ok(Some(x)) => Ok(x.split(';').any(|decl| value.is_match(decl.trim()))),
+                Ok(None) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
         })
     })
 }
 
-/// Predicate that returns true for elements that have all of the specified CSS properties with the
-/// specified values.
-/// See the `Needle` documentation for more details on text matching rules.
-pub fn element_has_css_properties<S, N>(
-    desired_css_properties: &[(S, N)],
+/// Predicate that returns true for elements that lack the specified attribute with the
+/// specified value. See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_attribute<S, N>(
+    attribute_name: S,
+    value: N,
     ignore_errors: bool,
 ) -> ElementPredicate
 where
-    S: Into<String> + Clone,
+    S: Into<String>,
     N: Needle + Clone + Send + Sync + 'static,
 {
-    let desired_css_properties: Vec<(String, N)> =
-        desired_css_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    let attribute_name: String = attribute_name.into();
     Box::new(move |elem| {
-        let desired_css_properties = desired_css_properties.clone();
+        let attribute_name: String = attribute_name.clone();
+        let value = value.clone();
         Box::pin(async move {
-            for (css_property_name, value) in &desired_css_properties {
-                match elem.get_css_property(css_property_name).await {
-                    Ok(x) => {
-                        if !value.is_match(&x) {
-                            return Ok(false);
-                        }
-                    }
-                    Err(e) => return handle_errors(Err(e), ignore_errors),
-                }
+            match elem.get_attribute(&attribute_name).await {
+                Ok(Some(x)) => Ok(!value.is_match(&x)),
+                Ok(None) => Ok(true),
+                Err(e) => handle_errors(Err(e), ignore_errors),
             }
-            Ok(true)
         })
     })
 }
 
-/// Predicate that returns true for elements that do not have any of the specified CSS properties
-/// with the specified values.
-/// See the `Needle` documentation for more details on text matching rules.
-pub fn element_lacks_css_properties<S, N>(
-    desired_css_properties: &[(S, N)],
+/// Predicate that returns true for elements where the specified function returns true for
+/// the value of the specified attribute. The function receives `None` if the element does
+/// not have the attribute, so it can decide how to treat a missing attribute.
+pub fn element_attribute_satisfies<S, F>(
+    attribute_name: S,
+    f: F,
     ignore_errors: bool,
 ) -> ElementPredicate
 where
-    S: Into<String> + Clone,
-    N: Needle + Clone + Send + Sync + 'static,
+    S: Into<String>,
+    F: Fn(Option<&str>) -> bool + Send + Sync + 'static,
 {
-    let desired_css_properties: Vec<(String, N)> =
-        desired_css_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    let attribute_name: String = attribute_name.into();
+    let f = Arc::new(f);
     Box::new(move |elem| {
-        let desired_css_properties = desired_css_properties.clone();
+        let attribute_name = attribute_name.clone();
+        let f = f.clone();
         Box::pin(async move {
-            for (css_property_name, value) in &desired_css_properties {
-                match elem.get_css_property(css_property_name).await {
-                    Ok(x) => {
-                        if value.is_match(&x) {
-                            return Ok(false);
-                        }
-                    }
-                    Err(e) => return handle_errors(Err(e), ignore_errors),
-                }
-            }
-            Ok(true)
+            handle_errors(
+                elem.get_attribute(&attribute_name).await.map(|x| f(x.as_deref())),
+                ignore_errors,
+            )
         })
     })
 }
+
+/// Script returning an object mapping each of the attribute names in `arguments[1]` to its
+/// value on the element, or null if the attribute is missing. Boolean attributes map to "true"
+/// when present, in the same way as the WebDriver Get Element Attribute command.
+const ATTRIBUTES_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const booleans = new Set([
+        "allowfullscreen", "async", "autofocus", "autoplay", "checked", "controls", "default",
+        "defer", "disabled", "formnovalidate", "hidden", "inert", "ismap", "itemscope", "loop",
+        "multiple", "muted", "nomodule", "novalidate", "open", "playsinline", "readonly",
+        "required", "reversed", "selected"
+    ]);
+    const result = {};
+    for (const name of arguments[1]) {
+        if (!elem.hasAttribute(name)) {
+            result[name] = null;
+        } else if (booleans.has(name.toLowerCase())) {
+            result[name] = "true";
+        } else {
+            result[name] = elem.getAttribute(name);
+        }
+    }
+    return result;
+"#;
+
+/// Script returning an object mapping each of the property names in `arguments[1]` to its
+/// value on the element, or null if the property is undefined.
+const PROPERTIES_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const result = {};
+    for (const name of arguments[1]) {
+        const value = elem[name];
+        result[name] = value === undefined ? null : value;
+    }
+    return result;
+"#;
+
+/// Execute the specified script with the element passed in as `arguments[0]` and the names
+/// passed in as `arguments[1]`, and return the object mapping each name to its value.
+async fn element_script_object(
+    elem: &WebElement<'_>,
+    script: &str,
+    names: Vec<String>,
+) -> WebDriverResult<Value> {
+    let mut args = ScriptArgs::new();
+    args.push(elem.clone())?;
+    args.push(names)?;
+    let ret = elem.session.execute_script_with_args(script, &args).await?;
+    Ok(ret.value().clone())
+}
+
+/// Check the values of all of the desired names, fetched with a single call to `execute`,
+/// which receives the names and returns an object mapping each name to its value. This
+/// returns true if all values match their needle (if `require_all` is true) or if none of
+/// them do (otherwise).
+async fn check_values<N, F, Fut>(
+    desired: &[(String, N)],
+    require_all: bool,
+    execute: F,
+) -> WebDriverResult<bool>
+where
+    N: Needle,
+    F: FnOnce(Vec<String>) -> Fut,
+    Fut: Future<Output = WebDriverResult<Value>>,
+{
+    let names: Vec<String> = desired.iter().map(|(name, _)| name.clone()).collect();
+    let object = execute(names.clone()).await?;
+    let values = lookup_values(&object, &names);
+    match require_all {
+        true => Ok(all_values_match(desired, &values)),
+        false => Ok(no_values_match(desired, &values)),
+    }
+}
+
+/// Look up each name in the specified object. Missing and null values are returned as None,
+/// and non-string values are converted to their JSON representation.
+fn lookup_values(object: &Value, names: &[String]) -> Vec<Option<String>> {
+    names
+        .iter()
+        .map(|name| match object.get(name) {
+            None | Some(Value::Null) => None,
+            Some(Value::String(x)) => Some(x.clone()),
+            Some(x) => Some(x.to_string()),
+        })
+        .collect()
+}
+
+/// Return true if every value is present and matches its corresponding needle.
+fn all_values_match<N: Needle>(desired: &[(String, N)], values: &[Option<String>]) -> bool {
+    desired
+        .iter()
+        .zip(values)
+        .all(|((_, needle), value)| value.as_deref().is_some_and(|x| needle.is_match(x)))
+}
+
+/// Return true if no value is present and matches its corresponding needle.
+fn no_values_match<N: Needle>(desired: &[(String, N)], values: &[Option<String>]) -> bool {
+    desired
+        .iter()
+        .zip(values)
+        .all(|((_, needle), value)| value.as_deref().is_none_or(|x| !needle.is_match(x)))
+}
+
+/// Predicate that returns true for elements that have all of the specified attributes with the
+/// specified values. See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_attributes<S, N>(
+    desired_attributes: &[(S, N)],
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String> + Clone,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let desired_attributes: Vec<(String, N)> =
+        desired_attributes.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    Box::new(move |elem| {
+        let desired_attributes = desired_attributes.clone();
+        Box::pin(async move {
+            let execute = |names| element_script_object(elem, ATTRIBUTES_SCRIPT, names);
+            handle_errors(check_values(&desired_attributes, true, execute).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that do not have any of the specified attributes with
+/// the specified values. See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_attributes<S, N>(
+    desired_attributes: &[(S, N)],
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String> + Clone,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let desired_attributes: Vec<(String, N)> =
+        desired_attributes.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    Box::new(move |elem| {
+        let desired_attributes = desired_attributes.clone();
+        Box::pin(async move {
+            let execute = |names| element_script_object(elem, ATTRIBUTES_SCRIPT, names);
+            handle_errors(check_values(&desired_attributes, false, execute).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the specified property with the specified
+/// value. See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_property<S, N>(
+    property_name: S,
+    value: N,
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String>,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let property_name: String = property_name.into();
+    Box::new(move |elem| {
+        let property_name = property_name.clone();
+        let value = value.clone();
+        Box::pin(async move {
+            match elem.get_property(&property_name).await {
+                Ok(Some(x)) => Ok(value.is_match(&x)),
+                Ok(None) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Predicate that returns true for elements that lack the specified property with the
+/// specified value. See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_property<S, N>(
+    property_name: S,
+    value: N,
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String>,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let property_name: String = property_name.into();
+    Box::new(move |elem| {
+        let property_name = property_name.clone();
+        let value = value.clone();
+        Box::pin(async move {
+            match elem.get_property(&property_name).await {
+                Ok(Some(x)) => Ok(!value.is_match(&x)),
+                Ok(None) => Ok(true),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have all of the specified properties with the
+/// specified value. See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_properties<S, N>(
+    desired_properties: &[(S, N)],
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String> + Clone,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let desired_properties: Vec<(String, N)> =
+        desired_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    Box::new(move |elem| {
+        let desired_properties = desired_properties.clone();
+        Box::pin(async move {
+            let execute = |names| element_script_object(elem, PROPERTIES_SCRIPT, names);
+            handle_errors(check_values(&desired_properties, true, execute).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that do not have any of the specified properties with
+/// the specified values. See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_properties<S, N>(
+    desired_properties: &[(S, N)],
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String> + Clone,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let desired_properties: Vec<(String, N)> =
+        desired_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    Box::new(move |elem| {
+        let desired_properties = desired_properties.clone();
+        Box::pin(async move {
+            let execute = |names| element_script_object(elem, PROPERTIES_SCRIPT, names);
+            handle_errors(check_values(&desired_properties, false, execute).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the specified CSS property with the specified
+/// value. See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_css_property<S, N>(
+    css_property_name: S,
+    value: N,
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String>,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let css_property_name: String = css_property_name.into();
+    Box::new(move |elem| {
+        let css_property_name = css_property_name.clone();
+        let value = value.clone();
+        Box::pin(async move {
+            handle_errors(
+                elem.get_css_property(&css_property_name).await.map(|x| value.is_match(&x)),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements that lack the specified CSS property with the
+/// specified value. See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_css_property<S, N>(
+    css_property_name: S,
+    value: N,
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String>,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let css_property_name: String = css_property_name.into();
+    Box::new(move |elem| {
+        let css_property_name = css_property_name.clone();
+        let value = value.clone();
+        Box::pin(async move {
+            handle_errors(
+                elem.get_css_property(&css_property_name).await.map(|x| !value.is_match(&x)),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose computed CSS `visibility` matches the
+/// specified value (e.g. "visible", "hidden" or "collapse").
+/// See the `Needle` documentation for more details on text matching rules.
+///
+/// Unlike `element_is_displayed()`, this only checks the `visibility` property, as returned by
+/// `WebElement::get_css_property()` (i.e. the computed style). It does not consider `display`,
+/// `opacity` or the visibility of ancestors.
+pub fn element_css_visibility_is<N>(visibility: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    element_has_css_property("visibility", visibility, ignore_errors)
+}
+
+/// Predicate that returns true for elements whose computed CSS `cursor` matches the specified
+/// value (e.g. "grab" or "move"), which UIs often use to signal that an element can be dragged.
+/// See the `Needle` documentation for more details on text matching rules.
+///
+/// The cursor is read via `WebElement::get_css_property()` (i.e. the computed style), so it
+/// reflects the value inherited from ancestors and set by stylesheets, not only inline styles.
+pub fn element_cursor_is<N>(cursor: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    element_has_css_property("cursor", cursor, ignore_errors)
+}
+
+/// Predicate that returns true for elements that are draggable via the HTML drag and drop API.
+///
+/// This reads the element's `draggable` property rather than the `draggable` attribute, so the
+/// browser's defaults are taken into account: images and links are draggable unless the
+/// attribute is set to `false`, while other elements require `draggable="true"`. Note that
+/// this does not detect drag and drop implemented purely with mouse or pointer events; use
+/// `element_cursor_is()` for UIs that signal draggability via the cursor instead.
+pub fn element_is_draggable(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(elem, "return arguments[0].draggable === true;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose computed CSS `opacity` is within `tolerance`
+/// of the specified value, e.g. `element_opacity_is(1.0, 0.01, true)` for a completed fade-in.
+///
+/// The opacity is read via `WebElement::get_css_property()` (i.e. the computed style), so it
+/// only reflects the element's own opacity, not the effective opacity inherited from its
+/// ancestors. A value that cannot be parsed as a number is treated as not matching.
+pub fn element_opacity_is(opacity: f64, tolerance: f64, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                elem.get_css_property("opacity").await.map(|x| match x.trim().parse::<f64>() {
+                    Ok(value) => (value - opacity).abs() <= tolerance,
+                    Err(_) => false,
+                }),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Named colors recognised by `parse_color()`, in addition to `rgb()`, `rgba()` and hex colors.
+/// Browsers return computed colors in `rgb()`/`rgba()` form, so this only needs to cover the
+/// CSS basic colors that are commonly written in tests.
+const NAMED_COLORS: &[(&str, (u8, u8, u8, u8))] = &[
+    ("transparent", (0, 0, 0, 0)),
+    ("black", (0, 0, 0, 255)),
+    ("silver", (192, 192, 192, 255)),
+    ("gray", (128, 128, 128, 255)),
+    ("grey", (128, 128, 128, 255)),
+    ("white", (255, 255, 255, 255)),
+    ("maroon", (128, 0, 0, 255)),
+    ("red", (255, 0, 0, 255)),
+    ("purple", (128, 0, 128, 255)),
+    ("fuchsia", (255, 0, 255, 255)),
+    ("green", (0, 128, 0, 255)),
+    ("lime", (0, 255, 0, 255)),
+    ("olive", (128, 128, 0, 255)),
+    ("yellow", (255, 255, 0, 255)),
+    ("navy", (0, 0, 128, 255)),
+    ("blue", (0, 0, 255, 255)),
+    ("teal", (0, 128, 128, 255)),
+    ("aqua", (0, 255, 255, 255)),
+    ("orange", (255, 165, 0, 255)),
+];
+
+/// Parse a hex color (without the leading `#`) in `rgb`, `rgba`, `rrggbb` or `rrggbbaa` form.
+fn parse_hex_color(hex: &str) -> Option<(u8, u8, u8, u8)> {
+    let digits: Vec<u8> =
+        hex.chars().map(|c| c.to_digit(16).map(|d| d as u8)).collect::<Option<_>>()?;
+    let mut channels: Vec<u8> = match digits.len() {
+        3 | 4 => digits.iter().map(|d| d * 17).collect(),
+        6 | 8 => digits.chunks(2).map(|pair| pair[0] * 16 + pair[1]).collect(),
+        _ => return None,
+    };
+    if channels.len() == 3 {
+        channels.push(255);
+    }
+    Some((channels[0], channels[1], channels[2], channels[3]))
+}
+
+/// Parse a color channel from `rgb()`, either as a number (0-255) or a percentage.
+fn parse_color_channel(value: &str) -> Option<u8> {
+    let value = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok()? / 100.0 * 255.0,
+        None => value.parse::<f64>().ok()?,
+    };
+    Some(value.round().max(0.0).min(255.0) as u8)
+}
+
+/// Parse an alpha value from `rgba()`, either as a number (0-1) or a percentage, and scale it
+/// to 0-255.
+fn parse_alpha_channel(value: &str) -> Option<u8> {
+    let value = match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+        None => value.parse::<f64>().ok()?,
+    };
+    Some((value.max(0.0).min(1.0) * 255.0).round() as u8)
+}
+
+/// Parse a CSS color into RGBA channels (0-255). Supports `rgb()` and `rgba()` in both the
+/// comma-separated and space-separated (`rgb(0 0 0 / 50%)`) syntax, hex colors and the named
+/// colors in `NAMED_COLORS`. The alpha channel is scaled from 0-1 to 0-255, and defaults to 255.
+fn parse_color(value: &str) -> Option<(u8, u8, u8, u8)> {
+    let value = value.trim().to_ascii_lowercase();
+    if let Some(hex) = value.strip_prefix('#') {
+        return parse_hex_color(hex);
+    }
+
+    let args = value.strip_prefix("rgba(").or_else(|| value.strip_prefix("rgb("));
+    if let Some(args) = args {
+        let parts: Vec<&str> = args
+            .strip_suffix(')')?
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|x| !x.is_empty())
+            .collect();
+        let alpha = match parts.len() {
+            3 => 255,
+            4 => parse_alpha_channel(parts[3])?,
+            _ => return None,
+        };
+        return Some((
+            parse_color_channel(parts[0])?,
+            parse_color_channel(parts[1])?,
+            parse_color_channel(parts[2])?,
+            alpha,
+        ));
+    }
+
+    NAMED_COLORS.iter().find(|(name, _)| *name == value).map(|(_, color)| *color)
+}
+
+/// Predicate that returns true for elements whose computed color for the specified CSS
+/// property (e.g. `color`, `background-color` or `border-top-color`) is within `tolerance` of
+/// the expected RGBA value on every channel, e.g. `element_color_is("color", (255, 255, 255,
+/// 255), 2, true)` for white text in a dark theme.
+///
+/// The computed value is parsed as `rgb()` or `rgba()` (comma or space separated), a hex
+/// color, or one of a small set of basic named colors, and then compared channel by channel.
+/// The alpha channel is scaled to 0-255 (so `rgba(0, 0, 0, 0.5)` has an alpha of 128) and is
+/// 255 if not specified. This is more robust than matching the computed string, since browsers
+/// differ in formatting and may round blended colors slightly differently.
+/// A value that cannot be parsed as a color is treated as not matching.
+pub fn element_color_is(
+    property: &str,
+    expected_rgba: (u8, u8, u8, u8),
+    tolerance: u8,
+    ignore_errors: bool,
+) -> ElementPredicate {
+    let property = property.to_string();
+    let (r, g, b, a) = expected_rgba;
+    let expected = [r, g, b, a];
+    Box::new(move |elem| {
+        let property = property.clone();
+        Box::pin(async move {
+            handle_errors(
+                elem.get_css_property(&property).await.map(|x| match parse_color(&x) {
+                    Some((r, g, b, a)) => {
+                        [r, g, b, a].iter().zip(expected.iter()).all(|(actual, expected)| {
+                            (*actual as i16 - *expected as i16).abs() <= tolerance as i16
+                        })
+                    }
+                    None => false,
+                }),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose computed CSS `z-index` is greater than or
+/// equal to the specified value. This can help diagnose overlays intercepting clicks.
+///
+/// A `z-index` of `auto` is treated as 0. Negative values compare numerically, so an element
+/// with `z-index: -1` does not satisfy `element_z_index_at_least(0, true)`. Note that this
+/// only reads the element's own computed `z-index`, which is not necessarily its effective
+/// stacking order, since that also depends on the stacking contexts of its ancestors.
+/// A value that cannot be parsed as an integer is treated as not matching.
+pub fn element_z_index_at_least(n: i64, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                elem.get_css_property("z-index").await.map(|x| match x.trim() {
+                    "auto" => 0 >= n,
+                    value => value.parse::<i64>().map(|value| value >= n).unwrap_or(false),
+                }),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have all of the specified CSS properties with the
+/// specified values.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_has_css_properties<S, N>(
+    desired_css_properties: &[(S, N)],
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String> + Clone,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let desired_css_properties: Vec<(String, N)> =
+        desired_css_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    Box::new(move |elem| {
+        let desired_css_properties = desired_css_properties.clone();
+        Box::pin(async move {
+            for (css_property_name, value) in &desired_css_properties {
+                match elem.get_css_property(css_property_name).await {
+                    Ok(x) => {
+                        if !value.is_match(&x) {
+                            return Ok(false);
+                        }
+                    }
+                    Err(e) => return handle_errors(Err(e), ignore_errors),
+                }
+            }
+            Ok(true)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that do not have any of the specified CSS properties
+/// with the specified values.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn element_lacks_css_properties<S, N>(
+    desired_css_properties: &[(S, N)],
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    S: Into<String> + Clone,
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    let desired_css_properties: Vec<(String, N)> =
+        desired_css_properties.iter().cloned().map(|(a, b)| (a.into(), b)).collect();
+    Box::new(move |elem| {
+        let desired_css_properties = desired_css_properties.clone();
+        Box::pin(async move {
+            for (css_property_name, value) in &desired_css_properties {
+                match elem.get_css_property(css_property_name).await {
+                    Ok(x) => {
+                        if value.is_match(&x) {
+                            return Ok(false);
+                        }
+                    }
+                    Err(e) => return handle_errors(Err(e), ignore_errors),
+                }
+            }
+            Ok(true)
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose computed `position` (e.g. `static`,
+/// `relative`, `absolute`, `fixed` or `sticky`) matches the specified needle. This is useful
+/// for waiting until a header becomes sticky or a toolbar becomes fixed on scroll.
+///
+/// NOTE: This reads the computed style, which may differ from the inline or author CSS,
+/// e.g. `position: sticky` is computed as `static` in browsers that do not support it.
+pub fn element_position_is<N>(position: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    element_has_css_property("position", position, ignore_errors)
+}
+
+/// Predicate that returns true for elements whose computed `direction` (`ltr` or `rtl`) is
+/// the specified direction. This is useful for waiting until the layout flips to right-to-left
+/// after switching locale.
+///
+/// NOTE: This reads the computed style, which is inherited from the `dir` attribute or CSS of
+/// an ancestor and may therefore differ from the element's own attributes. The direction alone
+/// does not prove that the layout has flipped, so for full RTL verification consider pairing
+/// this with a position check, e.g. `element_has_css_property("text-align", ..)` or a
+/// comparison of element rects.
+pub fn element_direction_is(direction: &str, ignore_errors: bool) -> ElementPredicate {
+    element_has_css_property("direction", direction.to_string(), ignore_errors)
+}
+
+/// Script returning true if none of the element's CSS animations is running, based on its
+/// computed `animation-name` and `animation-play-state`.
+const ANIMATION_STOPPED_SCRIPT: &str = r#"
+    const style = window.getComputedStyle(arguments[0]);
+    const names = style.animationName.split(",").map((x) => x.trim());
+    const states = style.animationPlayState.split(",").map((x) => x.trim());
+    // The play states are repeated as needed to match the number of animation names.
+    return names.every((name, i) => name === "none" || states[i % states.length] === "paused");
+"#;
+
+/// Predicate that returns true for elements whose CSS animations have stopped, e.g. a loading
+/// spinner that is animated via `@keyframes` while busy. This gives a readiness signal based on
+/// the styles applied by the page, rather than on the element's position or size.
+///
+/// This reads the computed `animation-name` and `animation-play-state`, and returns true if
+/// every animation name is `none` or its play state is `paused`. A removed element counts as
+/// stopped, so this also succeeds if the spinner is removed instead of restyled.
+///
+/// NOTE: Only CSS animations are detected. Animations driven by JavaScript (e.g. via
+/// `requestAnimationFrame`), the Web Animations API, CSS transitions, animated images and
+/// SVG animations do not show up in these properties. An animation that has finished its
+/// iterations still has its `animation-name`, so it is not considered stopped, and neither is
+/// an animation on an element hidden via `display: none`, even though it does not run.
+pub fn element_animation_stopped(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_missing(
+                element_script_bool(elem, ANIMATION_STOPPED_SCRIPT).await,
+                ErrorPolicy::TreatAs(true),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the specified computed ARIA role.
+/// See the `Needle` documentation for more details on text matching rules.
+///
+/// NOTE: thirtyfour does not currently expose the WebDriver "Get Computed Role" command, so
+/// the role is computed via JavaScript instead. This returns the explicit `role` attribute if
+/// present, otherwise the implicit role for common HTML elements. Unlike the browser's
+/// accessibility tree, it does not account for context-dependent roles (e.g. a `header`
+/// nested inside an `article`), `aria-hidden` or invalid role values.
+pub fn element_has_computed_role<N>(role: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let role = role.clone();
+        Box::pin(async move {
+            match element_script_string(elem, COMPUTED_ROLE_SCRIPT).await {
+                Ok(Some(x)) => Ok(role.is_match(&x)),
+                Ok(None) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Predicate that returns true for elements that have the specified computed accessible name
+/// (label). See the `Needle` documentation for more details on text matching rules.
+///
+/// NOTE: thirtyfour does not currently expose the WebDriver "Get Computed Label" command, so
+/// the label is computed via JavaScript instead. This considers `aria-labelledby`,
+/// `aria-label`, associated `label` elements, `alt`, text content and `title` (in that order),
+/// which is a simplified version of the full accessible name computation performed by the
+/// browser. In particular, hidden content and CSS generated content are not excluded.
+pub fn element_has_computed_label<N>(label: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let label = label.clone();
+        Box::pin(async move {
+            match element_script_string(elem, COMPUTED_LABEL_SCRIPT).await {
+                Ok(Some(x)) => Ok(label.is_match(&x)),
+                Ok(None) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Script returning true unless the element or any of its ancestors is hidden from assistive
+/// technology via `aria-hidden="true"` or the `inert` attribute.
+const NOT_ARIA_HIDDEN_SCRIPT: &str = r#"
+    for (let node = arguments[0]; node; node = node.parentElement) {
+        if (node.getAttribute("aria-hidden") === "true" || node.hasAttribute("inert")) {
+            return false;
+        }
+    }
+    return true;
+"#;
+
+/// Check whether the element is displayed and not hidden from assistive technology.
+async fn element_accessible(elem: &WebElement<'_>) -> WebDriverResult<bool> {
+    if !elem.is_displayed().await? {
+        return Ok(false);
+    }
+    element_script_bool(elem, NOT_ARIA_HIDDEN_SCRIPT).await
+}
+
+/// Predicate that returns true for elements that are exposed to assistive technology such as
+/// screen readers, i.e. that are displayed and not hidden via `aria-hidden`.
+///
+/// Since `aria-hidden="true"` hides the whole subtree, the element and each of its ancestors
+/// are checked, and an ancestor with `aria-hidden="true"` or the `inert` attribute makes the
+/// element inaccessible. Elements hidden via CSS (e.g. `display: none` on an ancestor) are
+/// excluded by the displayed check.
+///
+/// NOTE: This is a heuristic, not the browser's computed accessibility tree, which
+/// WebDriver does not expose. For example, it does not account for visually hidden but
+/// accessible content (which is exposed, but may not count as displayed), roles that hide
+/// their children (e.g. `role="presentation"`), or modal dialogs hiding the rest of the page.
+pub fn element_is_accessible(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move { handle_errors(element_accessible(elem).await, ignore_errors) })
+    })
+}
+
+/// Predicate that returns true for elements that are not exposed to assistive technology.
+/// See `element_is_accessible()` for more details.
+pub fn element_is_not_accessible(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move { negate(element_accessible(elem).await, ignore_errors) })
+    })
+}
+
+/// Predicate that returns true once the element has been removed from the accessibility tree,
+/// i.e. it or one of its ancestors has `aria-hidden="true"` or the `inert` attribute, or the
+/// element has been removed from the document. This is useful for verifying that opening a
+/// modal dialog hides the background content from screen readers.
+///
+/// The element and each of its ancestors (up to the document element) are checked, since
+/// `aria-hidden` hides the whole subtree. Unlike `element_is_not_accessible()`, this only
+/// considers these attributes and removal, not whether the element is displayed, so an
+/// element that is merely hidden via CSS does not count. An element that is no longer in the
+/// document (i.e. a no such element or stale element reference error) counts as success.
+pub fn element_becomes_inaccessible(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_missing(
+                element_script_bool(elem, NOT_ARIA_HIDDEN_SCRIPT).await.map(|x| !x),
+                ErrorPolicy::TreatAs(true),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Get the gap between two ranges along one axis, given the start and length of each, or 0
+/// if they overlap or touch.
+fn axis_gap(a_start: f64, a_len: f64, b_start: f64, b_len: f64) -> f64 {
+    (b_start - (a_start + a_len)).max(a_start - (b_start + b_len)).max(0.0)
+}
+
+/// Pair predicate that returns true if the two elements are within `max_gap` pixels of each
+/// other, both horizontally and vertically, e.g. for checking that a tooltip appears next to
+/// the button it describes. Overlapping elements have a gap of 0, so they are always
+/// adjacent. The gaps are measured between the element rects, so with a `max_gap` of 0 the
+/// elements must touch or overlap. See `BiElementWaiter`.
+pub fn elements_are_adjacent(max_gap: f64, ignore_errors: bool) -> ElementPairPredicate {
+    Box::new(move |first, second| {
+        Box::pin(async move {
+            let result = async {
+                let a = first.rect().await?;
+                let b = second.rect().await?;
+                let horizontal = axis_gap(a.x, a.width, b.x, b.width);
+                let vertical = axis_gap(a.y, a.height, b.y, b.height);
+                Ok(horizontal <= max_gap && vertical <= max_gap)
+            };
+            handle_errors(result.await, ignore_errors)
+        })
+    })
+}
+
+/// Create a predicate that compares the rect of each element against the rect of the specified
+/// other element. The comparison function receives the rect of the element being checked first.
+fn element_rect_compare<F>(other: &WebElement, compare: F, ignore_errors: bool) -> ElementPredicate
+where
+    F: Fn(&ElementRect, &ElementRect) -> bool + Send + Sync + 'static,
+{
+    let other_id = other.element_id.clone();
+    let compare = Arc::new(compare);
+    Box::new(move |elem| {
+        let other_id = other_id.clone();
+        let compare = compare.clone();
+        Box::pin(async move {
+            let other = WebElement::new(elem.session, other_id);
+            let result = match (elem.rect().await, other.rect().await) {
+                (Ok(a), Ok(b)) => Ok(compare(&a, &b)),
+                (Err(e), _) | (_, Err(e)) => Err(e),
+            };
+            handle_errors(result, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that are entirely above the specified other element.
+///
+/// Element rects are compared in CSS pixels relative to the top-left corner of the document,
+/// with y increasing downwards. An element is only considered above the other element if its
+/// bottom edge is at or above the top edge of the other element, so elements whose bounding
+/// boxes overlap vertically are never considered to be above (or below) one another.
+pub fn element_is_above(other: &WebElement, ignore_errors: bool) -> ElementPredicate {
+    element_rect_compare(other, |a, b| a.y + a.height <= b.y, ignore_errors)
+}
+
+/// Predicate that returns true for elements that are entirely below the specified other element.
+///
+/// See `element_is_above()` for details on the coordinate system and overlapping elements.
+pub fn element_is_below(other: &WebElement, ignore_errors: bool) -> ElementPredicate {
+    element_rect_compare(other, |a, b| a.y >= b.y + b.height, ignore_errors)
+}
+
+/// Predicate that returns true for elements that are entirely left of the specified other element.
+///
+/// Element rects are compared in CSS pixels relative to the top-left corner of the document,
+/// with x increasing to the right. An element is only considered left of the other element if
+/// its right edge is at or left of the left edge of the other element, so elements whose
+/// bounding boxes overlap horizontally are never considered to be left (or right) of one another.
+pub fn element_is_left_of(other: &WebElement, ignore_errors: bool) -> ElementPredicate {
+    element_rect_compare(other, |a, b| a.x + a.width <= b.x, ignore_errors)
+}
+
+/// Predicate that returns true for elements that are entirely right of the specified other
+/// element.
+///
+/// See `element_is_left_of()` for details on the coordinate system and overlapping elements.
+pub fn element_is_right_of(other: &WebElement, ignore_errors: bool) -> ElementPredicate {
+    element_rect_compare(other, |a, b| a.x >= b.x + b.width, ignore_errors)
+}
+
+/// Predicate that returns true if the title of the current page matches the specified title.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn driver_title<N>(title: N, ignore_errors: bool) -> DriverPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |session| {
+        let title = title.clone();
+        Box::pin(async move {
+            handle_errors(session.title().await.map(|x| title.is_match(&x)), ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true if the URL of the current page matches the specified URL.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn driver_url<N>(url: N, ignore_errors: bool) -> DriverPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |session| {
+        let url = url.clone();
+        Box::pin(async move {
+            handle_errors(session.current_url().await.map(|x| url.is_match(&x)), ignore_errors)
+        })
+    })
+}
+
+/// Script returning true if the document has loaded and no resource has finished loading
+/// within the last `arguments[0]` milliseconds.
+const NETWORK_IDLE_SCRIPT: &str = r#"
+    if (document.readyState !== 'complete') {
+        return false;
+    }
+    let latest = 0;
+    for (const entry of performance.getEntriesByType('resource')) {
+        latest = Math.max(latest, entry.startTime, entry.responseEnd);
+    }
+    return performance.now() - latest >= arguments[0];
+"#;
+
+/// Predicate that returns true once the current page appears to be idle on the network, i.e.
+/// the document has loaded and no new resource (including fetch and XHR requests) has been
+/// loaded for at least the `quiet` duration.
+///
+/// This is a heuristic based on the Resource Timing API (`performance.getEntriesByType`), so
+/// it requires JavaScript execution and has some limits:
+/// - Requests only appear once they have completed, so a single long-running request that
+///   is still in flight is not seen, and the page may be considered idle while it is pending.
+/// - WebSocket and server-sent event traffic is not recorded.
+/// - The browser stops recording entries once its resource timing buffer is full (250 entries
+///   by default), unless the page clears the buffer or increases its size.
+pub fn driver_network_idle(quiet: Duration, ignore_errors: bool) -> DriverPredicate {
+    let quiet_ms = quiet.as_millis() as u64;
+    Box::new(move |session| {
+        Box::pin(async move {
+            let mut args = ScriptArgs::new();
+            args.push(quiet_ms)?;
+            let result = session
+                .execute_script_with_args(NETWORK_IDLE_SCRIPT, &args)
+                .await
+                .map(|ret| ret.value() == &Value::Bool(true));
+            handle_errors(result, ignore_errors)
+        })
+    })
+}
+
+/// Script that installs a MutationObserver on the element (`arguments[0]`) if there isn't one
+/// already, and returns true (removing the observer) once no mutation has been observed for
+/// `arguments[1]` milliseconds.
+const DOM_QUIET_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const key = "__thirtyfourQueryDomQuiet";
+    let state = elem[key];
+    if (!state) {
+        state = { last: performance.now() };
+        state.observer = new MutationObserver(() => { state.last = performance.now(); });
+        state.observer.observe(elem, {
+            subtree: true,
+            childList: true,
+            attributes: true,
+            characterData: true
+        });
+        elem[key] = state;
+        return false;
+    }
+    if (performance.now() - state.last < arguments[1]) {
+        return false;
+    }
+    state.observer.disconnect();
+    delete elem[key];
+    return true;
+"#;
+
+/// Predicate that returns true for elements whose subtree has not changed for at least the
+/// `quiet` duration, e.g. for complex widgets that update the DOM several times after an
+/// interaction. This requires JavaScript execution.
+///
+/// The first check installs a `MutationObserver` on the element, which records the time of
+/// the last change to the element, its attributes or any of its descendants, and returns
+/// false. Subsequent checks return true once no change has been recorded for `quiet`, at
+/// which point the observer is disconnected and removed. This means at least two checks are
+/// needed, so the wait takes at least one poll interval even if the element is already quiet.
+///
+/// If the wait times out, the observer stays attached until the element is removed or the
+/// page is unloaded. A later wait on the same element reuses it, so changes that happened
+/// in between are taken into account.
+pub fn element_dom_quiet(quiet: Duration, ignore_errors: bool) -> ElementPredicate {
+    let quiet_ms = quiet.as_millis() as u64;
+    Box::new(move |elem| {
+        Box::pin(async move {
+            let mut args = ScriptArgs::new();
+            args.push(elem.clone())?;
+            args.push(quiet_ms)?;
+            let result = elem
+                .session
+                .execute_script_with_args(DOM_QUIET_SCRIPT, &args)
+                .await
+                .map(|ret| ret.value() == &Value::Bool(true));
+            handle_errors(result, ignore_errors)
+        })
+    })
+}
+
+/// Script that installs a listener for the event `arguments[1]` on the element
+/// (`arguments[0]`) if there isn't one already, and returns the number of such events the
+/// element has received since the listener was installed.
+const EVENT_COUNT_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const event = arguments[1];
+    const key = "__thirtyfourQueryEventCounts";
+    const counts = elem[key] || (elem[key] = {});
+    if (!(event in counts)) {
+        counts[event] = 0;
+        elem.addEventListener(event, () => { counts[event] += 1; }, true);
+    }
+    return counts[event];
+"#;
+
+/// Predicate that returns true for elements that have received exactly `n` events of the
+/// specified type (e.g. `"click"` or `"focus"`), for tests that need to verify how often an
+/// interaction happened. This requires JavaScript execution.
+///
+/// The first check installs a listener for the event on the element, which counts every
+/// event of that type dispatched to the element or (in the capture phase) to any of its
+/// descendants, so a click on an icon inside a button counts as a click on the button.
+/// Installation is idempotent: there is at most one listener per element and event type,
+/// and the count is shared by all predicates and waits for it, so it keeps counting across
+/// waits. The listener stays installed until the element is removed or the page is
+/// reloaded, and a re-rendered element starts again from zero.
+///
+/// NOTE: Events fired before the listener was installed cannot be counted, so check once
+/// (e.g. via `ElementWaiter::check()`) before triggering the events to be counted.
+pub fn element_event_count(event: &str, n: usize, ignore_errors: bool) -> ElementPredicate {
+    let event = event.to_string();
+    Box::new(move |elem| {
+        let event = event.clone();
+        Box::pin(async move {
+            let result = async {
+                let mut args = ScriptArgs::new();
+                args.push(elem.clone())?;
+                args.push(event)?;
+                let ret = elem.session.execute_script_with_args(EVENT_COUNT_SCRIPT, &args).await?;
+                Ok(ret.value().as_u64() == Some(n as u64))
+            };
+            handle_errors(result.await, ignore_errors)
+        })
+    })
+}
+
+/// Script returning `[selectionStart, selectionEnd]` for the element, or null if it does not
+/// support the selection API.
+const SELECTION_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    try {
+        const start = elem.selectionStart;
+        const end = elem.selectionEnd;
+        if (typeof start !== "number" || typeof end !== "number") {
+            return null;
+        }
+        return [start, end];
+    } catch (e) {
+        return null;
+    }
+"#;
+
+/// Return true if the value returned by `SELECTION_SCRIPT` is the specified range.
+fn is_selection(value: &Value, start: usize, end: usize) -> bool {
+    match value.as_array().map(|range| range.as_slice()) {
+        Some([s, e]) => s.as_u64() == Some(start as u64) && e.as_u64() == Some(end as u64),
+        _ => false,
+    }
+}
+
+/// Predicate that returns true for text inputs and textareas whose selection is the specified
+/// range, as given by their `selectionStart` and `selectionEnd` properties. A collapsed
+/// selection (`start == end`) is the caret position, e.g. `(0, 0)` for a caret at the start.
+///
+/// The offsets are in UTF-16 code units, as in JavaScript. Elements without the selection API,
+/// such as checkboxes, number inputs and other non-text elements, return false rather than an
+/// error, since their selection can never match. Note that the selection is tracked even while
+/// the element does not have focus, so combine this with `element_has_focus_within()` if focus
+/// matters as well. Contenteditable elements are not supported.
+pub fn element_selection_is(start: usize, end: usize, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            let result = element_script_value(elem, SELECTION_SCRIPT).await;
+            handle_errors(result.map(|value| is_selection(&value, start, end)), ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that contain at least one descendant matching
+/// the specified selector, i.e. the equivalent of the CSS `:has()` pseudo-class, without
+/// relying on browser support for it.
+///
+/// XPath selectors starting with `//` are rewritten to be relative to the element (see
+/// `ElementQuery::relative_xpath()`), since they would otherwise search the whole document.
+/// The selector must be `'static`, e.g. a string literal, or an owned `SelectorBy::Css` or
+/// `SelectorBy::XPath`. If the element has gone stale, the resulting error is treated as not
+/// matching if `ignore_errors` is true.
+pub fn element_has_descendant<B>(by: B, ignore_errors: bool) -> ElementPredicate
+where
+    B: Into<SelectorBy<'static>>,
+{
+    let by = by.into();
+    let by = by.to_relative_xpath().unwrap_or(by);
+    Box::new(move |elem| {
+        let by = by.clone();
+        Box::pin(async move {
+            handle_errors(
+                elem.find_elements(by.as_by()).await.map(|x| !x.is_empty()),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Script returning the horizontal scroll position of a carousel container (`arguments[0]`)
+/// in slides, given its first and second slides (`arguments[1]` and `arguments[2]`, which may
+/// be null), or null if the slide width is zero.
+const CAROUSEL_POSITION_SCRIPT: &str = r#"
+    const container = arguments[0];
+    const first = arguments[1];
+    const second = arguments[2];
+    const step = second
+        ? second.offsetLeft - first.offsetLeft
+        : first.getBoundingClientRect().width;
+    return step > 0 ? Math.abs(container.scrollLeft) / step : null;
+"#;
+
+/// Return true if the specified carousel position (in slides) is within `tolerance` slides
+/// of the specified index.
+fn is_carousel_at(position: f64, index: usize, tolerance: f64) -> bool {
+    (position - index as f64).abs() <= tolerance
+}
+
+/// Predicate for horizontally scrolling carousel containers that returns true once the slide
+/// at the specified 0-based index is active, i.e. the container is scrolled to it.
+///
+/// The slides are the descendants of the container matching `slide_selector`, which is made
+/// relative in the same way as for `element_has_descendant()`. The active position is
+/// computed as `scrollLeft / step`, where the step is the distance between the left edges of
+/// the first two slides (including any gap), or the width of the only slide. This assumes
+/// that all slides have the same width and that the first slide is at scroll position 0.
+/// `scrollLeft` is negative for right-to-left carousels in some browsers, so its absolute
+/// value is used.
+///
+/// The tolerance is in slides, e.g. 0.1 accepts a scroll position within 10% of a slide of
+/// the exact position, to allow for rounding and snap offsets. Returns false if there is no
+/// slide at the specified index.
+pub fn carousel_active_index<B>(
+    index: usize,
+    slide_selector: B,
+    tolerance: f64,
+    ignore_errors: bool,
+) -> ElementPredicate
+where
+    B: Into<SelectorBy<'static>>,
+{
+    let by = slide_selector.into();
+    let by = by.to_relative_xpath().unwrap_or(by);
+    Box::new(move |elem| {
+        let by = by.clone();
+        Box::pin(async move {
+            let result = async {
+                let slides = elem.find_elements(by.as_by()).await?;
+                if index >= slides.len() {
+                    return Ok(false);
+                }
+                let mut args = ScriptArgs::new();
+                args.push(elem.clone())?;
+                args.push(slides[0].clone())?;
+                args.push(slides.get(1).cloned())?;
+                let ret =
+                    elem.session.execute_script_with_args(CAROUSEL_POSITION_SCRIPT, &args).await?;
+                Ok(ret.value().as_f64().is_some_and(|x| is_carousel_at(x, index, tolerance)))
+            };
+            handle_errors(result.await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose screenshot matches the specified baseline
+/// PNG image, for visual regression tests. This requires the `visual` feature.
+///
+/// The difference is the fraction of pixels that differ in any channel (including alpha),
+/// between 0 and 1, and the element matches if it does not exceed `max_diff`, e.g. 0.01 to
+/// allow up to 1% of the pixels to differ, such as from anti-aliasing. A screenshot with
+/// different dimensions than the baseline never matches, so the baseline must be captured
+/// at the same size and device pixel ratio, e.g. using `WebElement::screenshot()` in the same
+/// browser and window size.
+///
+/// If the screenshot does not match, an image highlighting the differing pixels in red is
+/// saved next to the baseline, with the extension replaced by `diff.png`, overwriting the
+/// diff from any previous check. Failing to save it does not affect the result. With the
+/// `log` feature enabled, the failure is logged as a warning. Failing to read or decode the
+/// baseline is an error, which is handled according to `ignore_errors`.
+#[cfg(feature = "visual")]
+pub fn element_matches_baseline(
+    baseline_png: &Path,
+    max_diff: f64,
+    ignore_errors: bool,
+) -> ElementPredicate {
+    let baseline_png = baseline_png.to_path_buf();
+    Box::new(move |elem| {
+        let baseline_png = baseline_png.clone();
+        Box::pin(async move {
+            let result = async {
+                let actual = visual::decode_png(&elem.screenshot_as_png().await?)?;
+                let baseline = std::fs::read(&baseline_png).map_err(|e| {
+                    WebDriverError::CustomError(format!(
+                        "Failed to read baseline image {}: {}",
+                        baseline_png.display(),
+                        e
+                    ))
+                })?;
+                let diff = visual::compare_images(&actual, &visual::decode_png(&baseline)?);
+                if diff.ratio <= max_diff {
+                    return Ok(true);
+                }
+                if let Some(image) = diff.diff {
+                    let path = visual::diff_path(&baseline_png);
+                    let result = image.save(&path);
+                    #[cfg(feature = "log")]
+                    {
+                        if let Err(e) = result {
+                            log::warn!("Failed to save diff image to {}: {}", path.display(), e);
+                        }
+                    }
+                    #[cfg(not(feature = "log"))]
+                    {
+                        let _ = result;
+                    }
+                }
+                Ok(false)
+            };
+            handle_errors(result.await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for `select` elements that have a selected option with the
+/// specified text. For multi-select elements, this returns true if any of the selected
+/// options match. See the `Needle` documentation for more details on text matching rules.
+pub fn select_has_selected_text<N>(text: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let text = text.clone();
+        Box::pin(async move {
+            let options = match elem.find_elements(By::Tag("option")).await {
+                Ok(x) => x,
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            };
+
+            for option in options {
+                match option.is_selected().await {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    Err(e) => return handle_errors(Err(e), ignore_errors),
+                }
+
+                match option.text().await {
+                    Ok(x) => {
+                        if text.is_match(&x) {
+                            return Ok(true);
+                        }
+                    }
+                    Err(e) => return handle_errors(Err(e), ignore_errors),
+                }
+            }
+            Ok(false)
+        })
+    })
+}
+
+/// Predicate that returns true for `select` elements that have an option with the specified
+/// text, regardless of whether it is selected.
+/// See the `Needle` documentation for more details on text matching rules.
+pub fn select_has_option<N>(text: N, ignore_errors: bool) -> ElementPredicate
+where
+    N: Needle + Clone + Send + Sync + 'static,
+{
+    Box::new(move |elem| {
+        let text = text.clone();
+        Box::pin(async move {
+            let options = match elem.find_elements(By::Tag("option")).await {
+                Ok(x) => x,
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            };
+
+            for option in options {
+                match option.text().await {
+                    Ok(x) => {
+                        if text.is_match(&x) {
+                            return Ok(true);
+                        }
+                    }
+                    Err(e) => return handle_errors(Err(e), ignore_errors),
+                }
+            }
+            Ok(false)
+        })
+    })
+}
+
+/// Predicate that returns true for form fields that satisfy their HTML5 constraint validation
+/// (i.e. `element.validity.valid` is true). Elements that do not support constraint validation
+/// are never considered valid.
+pub fn element_is_valid(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(
+                    elem,
+                    "return !!arguments[0].validity && arguments[0].validity.valid;",
+                )
+                .await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for form fields that fail their HTML5 constraint validation
+/// (i.e. `element.validity.valid` is false). Elements that do not support constraint validation
+/// are never considered invalid.
+pub fn element_is_invalid_form_field(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(
+                    elem,
+                    "return !!arguments[0].validity && !arguments[0].validity.valid;",
+                )
+                .await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Tracks the number of consecutive observations with the same value, for predicates that
+/// wait for something to stop changing.
+#[derive(Debug)]
+pub(crate) struct StabilityTracker<T> {
+    last: Option<(T, usize)>,
+}
+
+impl<T: PartialEq> StabilityTracker<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            last: None,
+        }
+    }
+
+    /// Record an observation, and return the number of consecutive observations (including
+    /// this one) that had the same value.
+    pub(crate) fn observe(&mut self, value: T) -> usize {
+        let count = match &self.last {
+            Some((last, count)) if *last == value => count + 1,
+            _ => 1,
+        };
+        self.last = Some((value, count));
+        count
+    }
+}
+
+/// Predicate that returns true once the element's screenshot has been identical for the
+/// specified number of consecutive evaluations, e.g. once fonts, images and animations have
+/// finished loading.
+///
+/// This captures a PNG screenshot of the element on every evaluation (i.e. once per poll) and
+/// compares a hash of it against the previous capture, so it is relatively expensive. It also
+/// requires the driver to support element screenshots.
+///
+/// NOTE: This predicate keeps track of previous captures, so a new predicate should be
+/// created for each wait.
+pub fn element_screenshot_stable(polls: usize, ignore_errors: bool) -> ElementPredicate {
+    let tracker = Arc::new(Mutex::new(StabilityTracker::new()));
+    Box::new(move |elem| {
+        let tracker = tracker.clone();
+        Box::pin(async move {
+            let png = match elem.screenshot_as_png().await {
+                Ok(x) => x,
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            };
+            let mut hasher = DefaultHasher::new();
+            png.hash(&mut hasher);
+            let count = tracker.lock().unwrap().observe(hasher.finish());
+            Ok(count >= polls)
+        })
+    })
+}
+
+/// Predicate that returns true once the element's text has been identical for the specified
+/// number of consecutive evaluations, e.g. once a typewriter animation or live transcription
+/// has settled.
+///
+/// Since there is one evaluation per poll, the text must stay unchanged for at least
+/// `polls - 1` poll intervals, which is also the minimum delay before this can succeed. A
+/// higher number of polls is more robust against pauses in the updates, at the cost of that
+/// latency, so choose it together with the poll interval. A value of 0 or 1 accepts the first
+/// successful read. Failed reads are handled according to `ignore_errors`, and do not count
+/// as a change.
+///
+/// NOTE: This predicate keeps track of previous reads, so a new predicate should be created
+/// for each wait.
+pub fn element_text_stable(polls: usize, ignore_errors: bool) -> ElementPredicate {
+    let tracker = Arc::new(Mutex::new(StabilityTracker::new()));
+    Box::new(move |elem| {
+        let tracker = tracker.clone();
+        Box::pin(async move {
+            let text = match elem.text().await {
+                Ok(x) => x,
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            };
+            let count = tracker.lock().unwrap().observe(text);
+            Ok(count >= polls)
+        })
+    })
+}
+
+/// Predicate that returns true for tabs that are selected, i.e. that have the attribute
+/// `aria-selected="true"`.
+///
+/// See also `element_is_selected_tab_with_panel()`, which also checks the associated panel.
+pub fn element_is_selected_tab(ignore_errors: bool) -> ElementPredicate {
+    element_has_attribute("aria-selected", "true", ignore_errors)
+}
+
+/// Predicate that returns true for tabs that are selected (i.e. that have the attribute
+/// `aria-selected="true"`), and whose associated panel is displayed.
+///
+/// The panel is the element whose id is referenced by the tab's `aria-controls` attribute.
+/// If the tab does not have an `aria-controls` attribute, only the `aria-selected` attribute
+/// is checked. If the referenced panel does not exist, this returns false.
+pub fn element_is_selected_tab_with_panel(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            match elem.get_attribute("aria-selected").await {
+                Ok(Some(x)) if x == "true" => {}
+                Ok(_) => return Ok(false),
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            }
+
+            let panel_id = match elem.get_attribute("aria-controls").await {
+                Ok(Some(x)) => x,
+                Ok(None) => return Ok(true),
+                Err(e) => return handle_errors(Err(e), ignore_errors),
+            };
+
+            match elem.session.find_element(By::Id(&panel_id)).await {
+                Ok(panel) => handle_errors(panel.is_displayed().await, ignore_errors),
+                Err(WebDriverError::NoSuchElement(_)) => Ok(false),
+                Err(e) => handle_errors(Err(e), ignore_errors),
+            }
+        })
+    })
+}
+
+/// Predicate that returns true for elements that are editable via `contenteditable`, as used
+/// by rich-text editors.
+///
+/// This reads the element's `isContentEditable` property rather than the `contenteditable`
+/// attribute, so the tri-state attribute is resolved by the browser: `true` (or an empty
+/// value) makes the element editable, `false` makes it non-editable, and `inherit` (or no
+/// attribute at all) inherits the state of the parent element.
+pub fn element_is_contenteditable(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_bool(elem, "return arguments[0].isContentEditable;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements that are not editable via `contenteditable`.
+/// See `element_is_contenteditable()` for more details.
+pub fn element_is_not_contenteditable(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            negate(
+                element_script_bool(elem, "return arguments[0].isContentEditable;").await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Script returning true if the element at the center of the element's bounding rect is the
+/// element itself or one of its descendants.
+const NOT_OBSCURED_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const rect = elem.getBoundingClientRect();
+    const hit = document.elementFromPoint(rect.left + rect.width / 2, rect.top + rect.height / 2);
+    return hit !== null && elem.contains(hit);
+"#;
+
+/// Script returning true if the element at the center of the element's bounding rect is
+/// exactly the element itself.
+const TOPMOST_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const rect = elem.getBoundingClientRect();
+    const hit = document.elementFromPoint(rect.left + rect.width / 2, rect.top + rect.height / 2);
+    return hit === elem;
+"#;
+
+/// Predicate that returns true for elements that are not obscured by another element at their
+/// center, i.e. the element found at the center of the element's bounding rect is either the
+/// element itself or one of its descendants. This is usually what matters for clicking, since
+/// a click on a descendant also reaches the element.
+///
+/// The center must be inside the viewport, so an element scrolled out of view does not match.
+/// See also `element_is_topmost()`, which does not accept descendants.
+pub fn element_is_not_obscured(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(element_script_bool(elem, NOT_OBSCURED_SCRIPT).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true for elements that are the topmost element at their center, i.e.
+/// the element found at the center of the element's bounding rect is exactly the element
+/// itself, not a descendant or an ancestor. This is the precise condition for a click at the
+/// center hitting exactly this element (e.g. as the event target).
+///
+/// Unlike `element_is_not_obscured()`, this does not match if a descendant (e.g. an icon or
+/// text span inside a button) covers the center. The center must be inside the viewport.
+pub fn element_is_topmost(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(element_script_bool(elem, TOPMOST_SCRIPT).await, ignore_errors)
+        })
+    })
+}
+
+/// The axis (or axes) checked by `element_is_overflowing()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverflowAxis {
+    /// Content overflows horizontally, i.e. `scrollWidth > clientWidth`.
+    Horizontal,
+    /// Content overflows vertically, i.e. `scrollHeight > clientHeight`.
+    Vertical,
+    /// Content overflows horizontally or vertically (or both).
+    Either,
+}
+
+/// The default CSS selector for the busy marker used by `element_not_submitting()`.
+pub const DEFAULT_BUSY_SELECTOR: &str = r#"[aria-busy="true"]"#;
+
+/// Script returning true if neither the element (`arguments[0]`) nor its form matches the
+/// busy selector (`arguments[1]`).
+const NOT_BUSY_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const selector = arguments[1];
+    const form = elem.form || elem.closest("form");
+    return !elem.matches(selector) && !(form && form.matches(selector));
+"#;
+
+/// Predicate that returns true for elements (typically submit buttons) that are enabled and
+/// are not marked as busy, i.e. neither the element nor its form has `aria-busy="true"`.
+/// See `element_not_submitting_with()` to use a different busy marker.
+///
+/// NOTE: "Submitting" is not a standard DOM state, so this is a heuristic based on common
+/// conventions: buttons are usually disabled and/or marked busy while the form is submitted.
+/// If the application does not do either, this condition passes immediately.
+pub fn element_not_submitting(ignore_errors: bool) -> ElementPredicate {
+    element_not_submitting_with(DEFAULT_BUSY_SELECTOR, ignore_errors)
+}
+
+/// Predicate that returns true for elements that are enabled and where neither the element
+/// nor its form matches the specified CSS selector, e.g. `".is-loading"` or
+/// `"[data-state='submitting']"`. See `element_not_submitting()`.
+pub fn element_not_submitting_with(busy_selector: &str, ignore_errors: bool) -> ElementPredicate {
+    let busy_selector = busy_selector.to_string();
+    Box::new(move |elem| {
+        let busy_selector = busy_selector.clone();
+        Box::pin(async move {
+            let result = async {
+                if !elem.is_enabled().await? {
+                    return Ok(false);
+                }
+                let mut args = ScriptArgs::new();
+                args.push(elem.clone())?;
+                args.push(busy_selector)?;
+                let ret = elem.session.execute_script_with_args(NOT_BUSY_SCRIPT, &args).await?;
+                Ok(ret.value() == &Value::Bool(true))
+            };
+            handle_errors(result.await, ignore_errors)
+        })
+    })
+}
+
+/// Script returning the 0-based index of an element among its parent's element children,
+/// or -1 if it has no parent.
+const CHILD_INDEX_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const parent = elem.parentElement;
+    return parent ? Array.prototype.indexOf.call(parent.children, elem) : -1;
+"#;
+
+/// Predicate that returns true for elements that are the nth child of their parent, e.g. to
+/// verify that a row landed in the expected position after sorting or reordering.
+///
+/// NOTE: Unlike the CSS `:nth-child()` selector, `n` is 0-based, so `element_is_nth_child(0, ..)`
+/// matches the first child. Only element siblings are counted, not text or comment nodes.
+/// An element without a parent element (e.g. `<html>`) never matches.
+pub fn element_is_nth_child(n: usize, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_value(elem, CHILD_INDEX_SCRIPT)
+                    .await
+                    .map(|x| x.as_u64() == Some(n as u64)),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Script returning the scroll and client dimensions of an element as
+/// `[scrollWidth, clientWidth, scrollHeight, clientHeight]`.
+const OVERFLOW_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    return [elem.scrollWidth, elem.clientWidth, elem.scrollHeight, elem.clientHeight];
+"#;
+
+/// Return true if the dimensions returned by `OVERFLOW_SCRIPT` overflow along the specified
+/// axis. Returns false if the value is not an array of four numbers.
+fn is_overflowing(axis: OverflowAxis, dimensions: &Value) -> bool {
+    let dimensions: Vec<u64> = match dimensions.as_array() {
+        Some(x) => x.iter().filter_map(|v| v.as_u64()).collect(),
+        None => return false,
+    };
+    if let [scroll_width, client_width, scroll_height, client_height] = dimensions[..] {
+        let horizontal = scroll_width > client_width;
+        let vertical = scroll_height > client_height;
+        match axis {
+            OverflowAxis::Horizontal => horizontal,
+            OverflowAxis::Vertical => vertical,
+            OverflowAxis::Either => horizontal || vertical,
+        }
+    } else {
+        false
+    }
+}
+
+/// Predicate that returns true for elements whose content overflows along the specified axis,
+/// e.g. text that is clipped or truncated with an ellipsis, or a container that has become
+/// scrollable. The comparison is `scrollWidth > clientWidth` for `OverflowAxis::Horizontal`
+/// and `scrollHeight > clientHeight` for `OverflowAxis::Vertical`.
+///
+/// NOTE: The browser rounds these dimensions to whole pixels, so an overflow of less than one
+/// pixel may not be detected. Elements without a CSS layout box (e.g. inline elements) report
+/// zero for all dimensions and are never considered overflowing.
+pub fn element_is_overflowing(axis: OverflowAxis, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(
+                element_script_value(elem, OVERFLOW_SCRIPT).await.map(|x| is_overflowing(axis, &x)),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose content does not overflow along the
+/// specified axis. See `element_is_overflowing()`.
+pub fn element_is_not_overflowing(axis: OverflowAxis, ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            negate(
+                element_script_value(elem, OVERFLOW_SCRIPT).await.map(|x| is_overflowing(axis, &x)),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Script returning the state of an element for a consistent snapshot, or null if the
+/// element is no longer connected to the document. See `ElementWaiter::consistent_snapshot()`.
+const SNAPSHOT_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    if (!elem.isConnected) {
+        return null;
+    }
+    const style = window.getComputedStyle(elem);
+    const rect = elem.getBoundingClientRect();
+    const attributes = {};
+    for (const attr of elem.attributes) {
+        attributes[attr.name] = attr.value;
+    }
+    return {
+        displayed: style.display !== "none" && style.visibility !== "hidden"
+            && rect.width > 0 && rect.height > 0,
+        enabled: !elem.matches(":disabled"),
+        selected: !!(elem.checked || elem.selected),
+        text: elem.innerText,
+        rect: {
+            x: rect.left + window.scrollX,
+            y: rect.top + window.scrollY,
+            width: rect.width,
+            height: rect.height
+        },
+        attributes: attributes
+    };
+"#;
+
+/// Convert the value returned by `SNAPSHOT_SCRIPT` into a snapshot.
+fn parse_snapshot(value: &Value) -> ElementStateSnapshot {
+    if !value.is_object() {
+        return ElementStateSnapshot::missing();
+    }
+    let attributes: BTreeMap<String, String> = value["attributes"]
+        .as_object()
+        .map(|x| {
+            x.iter()
+                .filter_map(|(name, v)| v.as_str().map(|v| (name.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    ElementStateSnapshot {
+        present: true,
+        displayed: value["displayed"].as_bool(),
+        enabled: value["enabled"].as_bool(),
+        selected: value["selected"].as_bool(),
+        text: value["text"].as_str().map(|x| x.to_string()),
+        rect: serde_json::from_value(value["rect"].clone()).ok(),
+        attributes: Some(attributes),
+    }
+}
+
+/// Read a snapshot of the state of the element, either in a single script if `consistent` is
+/// true, or otherwise using one WebDriver request per state. In the latter case only an
+/// error checking whether the element is present is returned.
+pub(crate) async fn element_state_snapshot(
+    elem: &WebElement<'_>,
+    consistent: bool,
+) -> WebDriverResult<ElementStateSnapshot> {
+    if consistent {
+        return match element_script_value(elem, SNAPSHOT_SCRIPT).await {
+            Ok(x) => Ok(parse_snapshot(&x)),
+            Err(WebDriverError::StaleElementReference(_))
+            | Err(WebDriverError::NoSuchElement(_)) => Ok(ElementStateSnapshot::missing()),
+            Err(e) => Err(e),
+        };
+    }
+
+    if !elem.is_present().await? {
+        return Ok(ElementStateSnapshot::missing());
+    }
+    Ok(ElementStateSnapshot {
+        present: true,
+        displayed: elem.is_displayed().await.ok(),
+        enabled: elem.is_enabled().await.ok(),
+        selected: elem.is_selected().await.ok(),
+        text: elem.text().await.ok(),
+        rect: elem.rect().await.ok(),
+        attributes: None,
+    })
+}
+
+/// Predicate that returns true for elements whose state snapshot satisfies the specified
+/// function. The snapshot is read once per check, so all states checked by the function come
+/// from the same read. If `consistent` is true, the snapshot is read in a single script so
+/// that the states are also consistent with each other. See
+/// `ElementWaiter::consistent_snapshot()` for the state that is included.
+pub fn element_snapshot_matches<F>(f: F, consistent: bool, ignore_errors: bool) -> ElementPredicate
+where
+    F: Fn(&ElementStateSnapshot) -> bool + Send + Sync + 'static,
+{
+    let f = Arc::new(f);
+    Box::new(move |elem| {
+        let f = f.clone();
+        Box::pin(async move {
+            handle_errors(
+                element_state_snapshot(elem, consistent).await.map(|x| f(&x)),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// The checks performed by `element_is_ready_with()`. All checks are enabled by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadyOptions {
+    /// Require the element to be displayed. See `element_is_displayed()`.
+    pub displayed: bool,
+    /// Require the element to be enabled. See `element_is_enabled()`.
+    pub enabled: bool,
+    /// Require the element's rect to be unchanged since the previous check, i.e. the element
+    /// is not moving or resizing, e.g. due to an animation or the page still loading.
+    pub rect_stable: bool,
+    /// Require the element not to be obscured at its center. See `element_is_not_obscured()`.
+    pub not_obscured: bool,
+}
+
+impl Default for ReadyOptions {
+    fn default() -> Self {
+        Self {
+            displayed: true,
+            enabled: true,
+            rect_stable: true,
+            not_obscured: true,
+        }
+    }
+}
+
+/// Predicate that returns true for elements that are ready to be interacted with, i.e. they
+/// are present, displayed, enabled, not moving and not obscured. This combines the most
+/// commonly needed checks into one condition. See `element_is_ready_with()` to choose which
+/// checks are performed.
+pub fn element_is_ready(ignore_errors: bool) -> ElementPredicate {
+    element_is_ready_with(ReadyOptions::default(), ignore_errors)
+}
+
+/// Predicate that returns true for elements that are present and pass the checks enabled in
+/// the specified options. The checks run in the order of the fields of `ReadyOptions`, and
+/// stop at the first check that fails.
+///
+/// An element that is no longer present (i.e. a no such element or stale element reference
+/// error) is treated as not ready rather than as an error. Any other error is handled
+/// according to `ignore_errors`.
+///
+/// The rect stability check compares the rect with the one seen by the previous check, so it
+/// can only pass from the second check onwards, and a new predicate should be created for
+/// each wait.
+pub fn element_is_ready_with(options: ReadyOptions, ignore_errors: bool) -> ElementPredicate {
+    let last_rect: Arc<Mutex<Option<ElementRect>>> = Arc::new(Mutex::new(None));
+    Box::new(move |elem| {
+        let last_rect = last_rect.clone();
+        Box::pin(async move {
+            let result = async {
+                if options.displayed && !elem.is_displayed().await? {
+                    return Ok(false);
+                }
+                if options.enabled && !elem.is_enabled().await? {
+                    return Ok(false);
+                }
+                if options.rect_stable {
+                    let rect = elem.rect().await?;
+                    let mut last_rect = last_rect.lock().unwrap();
+                    let is_stable = last_rect.as_ref().is_some_and(|last| {
+                        last.x == rect.x
+                            && last.y == rect.y
+                            && last.width == rect.width
+                            && last.height == rect.height
+                    });
+                    *last_rect = Some(rect);
+                    if !is_stable {
+                        return Ok(false);
+                    }
+                }
+                if options.not_obscured && !element_script_bool(elem, NOT_OBSCURED_SCRIPT).await? {
+                    return Ok(false);
+                }
+                // If no checks are enabled, this still requires the element to be present.
+                elem.tag_name().await.map(|_| true)
+            };
+            handle_missing(result.await, ErrorPolicy::TreatAs(false), ignore_errors)
+        })
+    })
+}
+
+/// Script returning true if the element or any of its descendants has focus.
+const FOCUS_WITHIN_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const active = document.activeElement;
+    return active !== null && (elem === active || elem.contains(active));
+"#;
+
+/// Predicate that returns true for elements that have focus themselves, or that contain the
+/// focused element, i.e. the same semantics as the CSS `:focus-within` pseudo-class. This is
+/// useful for composite widgets such as comboboxes and menus, where focus may be on any of
+/// their parts.
+///
+/// This compares against `document.activeElement` rather than using `:focus-within` directly,
+/// so it does not depend on browser support for the pseudo-class. Note that focus inside an
+/// iframe or a shadow root only counts as far as the host element is the active element of
+/// this document.
+pub fn element_has_focus_within(ignore_errors: bool) -> ElementPredicate {
+    Box::new(move |elem| {
+        Box::pin(async move {
+            handle_errors(element_script_bool(elem, FOCUS_WITHIN_SCRIPT).await, ignore_errors)
+        })
+    })
+}
+
+/// Predicate that returns true once the element is no longer present, or its text differs from
+/// the specified baseline text. This is useful for waiting until a notification is dismissed
+/// or replaced, regardless of whether it is removed or updated in place.
+///
+/// A no such element or stale element reference error counts as the element being gone, so it
+/// is treated as success rather than an error. Any other error is handled according to
+/// `ignore_errors`.
+pub fn element_gone_or_text_changed<S>(baseline_text: S, ignore_errors: bool) -> ElementPredicate
+where
+    S: Into<String>,
+{
+    let baseline_text: String = baseline_text.into();
+    Box::new(move |elem| {
+        let baseline_text = baseline_text.clone();
+        Box::pin(async move {
+            handle_missing(
+                elem.text().await.map(|x| x != baseline_text),
+                ErrorPolicy::TreatAs(true),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Script returning true if the element is fully within the client area of the container.
+/// A tolerance of half a pixel is allowed to account for sub-pixel rounding.
+const FULLY_WITHIN_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    const container = arguments[1];
+    const r = elem.getBoundingClientRect();
+    const c = container.getBoundingClientRect();
+    const left = c.left + container.clientLeft;
+    const top = c.top + container.clientTop;
+    const right = left + container.clientWidth;
+    const bottom = top + container.clientHeight;
+    const tolerance = 0.5;
+    return r.left >= left - tolerance
+        && r.top >= top - tolerance
+        && r.right <= right + tolerance
+        && r.bottom <= bottom + tolerance;
+"#;
+
+/// Predicate that returns true for elements that are fully within the visible area of the
+/// specified scroll container, e.g. the active slide of a carousel.
+///
+/// The element's bounding rect is compared against the container's client area (i.e.
+/// excluding its borders and scrollbars), rather than the viewport. The element must be
+/// entirely inside the client area to match, so an element that is only partially scrolled
+/// into view does not match. Note that this does not check whether the container itself is
+/// visible in the viewport.
+pub fn element_fully_within(container: &WebElement, ignore_errors: bool) -> ElementPredicate {
+    let container_id = container.element_id.clone();
+    Box::new(move |elem| {
+        let container_id = container_id.clone();
+        Box::pin(async move {
+            let container = WebElement::new(elem.session, container_id);
+            handle_errors(
+                element_pair_script_bool(elem, &container, FULLY_WITHIN_SCRIPT).await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate that returns true for elements whose `value` property equals the `value`
+/// property of the specified other element, e.g. a "confirm password" field matching the
+/// password field.
+///
+/// Both values are read in a single script on each poll, so they are compared at the same
+/// point in time. If either element has gone stale, the script returns an error, which is
+/// treated as not matching if `ignore_errors` is true. Note that a re-rendered `other` element
+/// is not found again, so pass an element that will stay attached to the document.
+pub fn element_value_equals_element(other: &WebElement, ignore_errors: bool) -> ElementPredicate {
+    let other_id = other.element_id.clone();
+    Box::new(move |elem| {
+        let other_id = other_id.clone();
+        Box::pin(async move {
+            let other = WebElement::new(elem.session, other_id);
+            handle_errors(
+                element_pair_script_bool(
+                    elem,
+                    &other,
+                    "return arguments[0].value === arguments[1].value;",
+                )
+                .await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Script returning the rendered widths of `arguments[0]` and `arguments[1]` as an array.
+const WIDTHS_SCRIPT: &str = r#"
+    return [
+        arguments[0].getBoundingClientRect().width,
+        arguments[1].getBoundingClientRect().width
+    ];
+"#;
+
+/// Return true if `width` is within `tolerance` of the specified fraction of `parent_width`.
+/// Always returns false if the parent has no width.
+fn is_width_fraction(width: f64, parent_width: f64, fraction: f64, tolerance: f64) -> bool {
+    parent_width > 0.0 && (width / parent_width - fraction).abs() <= tolerance
+}
+
+/// Predicate that returns true for elements whose width is the specified fraction of the
+/// width of `parent`, e.g. 0.25 for a sidebar that takes up a quarter of its container. This
+/// is useful for waiting out responsive reflows until the layout reaches the expected
+/// proportion.
+///
+/// The tolerance is absolute, in the same units as the fraction, so a fraction of 0.25 with a
+/// tolerance of 0.01 accepts anything from 24% to 26%. This compares the rendered widths from
+/// `getBoundingClientRect()`, read in a single script, not the declared CSS percentages, so
+/// padding, borders and transforms are included. `parent` does not have to be the DOM parent.
+pub fn element_width_fraction_of(
+    parent: &WebElement,
+    fraction: f64,
+    tolerance: f64,
+    ignore_errors: bool,
+) -> ElementPredicate {
+    let parent_id = parent.element_id.clone();
+    Box::new(move |elem| {
+        let parent_id = parent_id.clone();
+        Box::pin(async move {
+            let parent = WebElement::new(elem.session, parent_id);
+            handle_errors(
+                element_pair_script_value(elem, &parent, WIDTHS_SCRIPT).await.map(|x| {
+                    match (x[0].as_f64(), x[1].as_f64()) {
+                        (Some(width), Some(parent_width)) => {
+                            is_width_fraction(width, parent_width, fraction, tolerance)
+                        }
+                        _ => false,
+                    }
+                }),
+                ignore_errors,
+            )
+        })
+    })
+}
+
+/// Predicate for scroll containers that returns true once the specified child is fully within
+/// the container's client area, e.g. after calling `scrollIntoView()` on a child of a
+/// virtualized list, where scrolling happens asynchronously.
+///
+/// This is the same check as `element_fully_within()`, but evaluated on the container rather
+/// than on the child. It is relative to the container, not the viewport, so it does not check
+/// whether the container itself is in view.
+pub fn element_child_scrolled_into_view(
+    child: &WebElement,
+    ignore_errors: bool,
+) -> ElementPredicate {
+    let child_id = child.element_id.clone();
+    Box::new(move |elem| {
+        let child_id = child_id.clone();
+        Box::pin(async move {
+            let child = WebElement::new(elem.session, child_id);
+            handle_errors(
+                element_pair_script_bool(&child, elem, FULLY_WITHIN_SCRIPT).await,
+                ignore_errors,
+            )
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_class_set() {
+        let expected: HashSet<&str> = ["btn", "active"].iter().copied().collect();
+        assert_eq!(class_set("  btn\tactive btn\n"), expected);
+        assert!(class_set("").is_empty());
+    }
+
+    #[test]
+    fn test_lookup_values() {
+        let object = serde_json::json!({"id": "main", "hidden": null, "tabIndex": 0});
+        let names: Vec<String> =
+            ["id", "hidden", "tabIndex", "missing"].iter().map(|x| x.to_string()).collect();
+        assert_eq!(
+            lookup_values(&object, &names),
+            vec![Some("main".to_string()), None, Some("0".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_check_values_executes_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let desired: Vec<(String, &str)> = vec![
+            ("id".to_string(), "main"),
+            ("role".to_string(), "button"),
+            ("title".to_string(), "Save"),
+        ];
+        let object = serde_json::json!({"id": "main", "role": "button", "title": "Save"});
+        let executions = AtomicUsize::new(0);
+        let execute = |names: Vec<String>| {
+            executions.fetch_add(1, Ordering::SeqCst);
+            assert_eq!(names, vec!["id", "role", "title"]);
+            futures::future::ready(Ok(object.clone()))
+        };
+
+        // One script execution per check, regardless of the number of names.
+        let check =
+            |require_all| futures::executor::block_on(check_values(&desired, require_all, execute));
+        assert!(check(true).unwrap());
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+        assert!(!check(false).unwrap());
+        assert_eq!(executions.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_values_match() {
+        let desired = vec![("id".to_string(), "main"), ("role".to_string(), "button")];
+        let values = vec![Some("main".to_string()), Some("button".to_string())];
+        assert!(all_values_match(&desired, &values));
+        assert!(!no_values_match(&desired, &values));
+
+        let values = vec![Some("main".to_string()), None];
+        assert!(!all_values_match(&desired, &values));
+        assert!(!no_values_match(&desired, &values));
+
+        let values = vec![Some("other".to_string()), None];
+        assert!(!all_values_match(&desired, &values));
+        assert!(no_values_match(&desired, &values));
+    }
+
+    #[test]
+    fn test_is_overflowing() {
+        let clipped = serde_json::json!([300, 200, 50, 50]);
+        assert!(is_overflowing(OverflowAxis::Horizontal, &clipped));
+        assert!(!is_overflowing(OverflowAxis::Vertical, &clipped));
+        assert!(is_overflowing(OverflowAxis::Either, &clipped));
+
+        let fits = serde_json::json!([200, 200, 40, 50]);
+        assert!(!is_overflowing(OverflowAxis::Either, &fits));
+        assert!(!is_overflowing(OverflowAxis::Either, &serde_json::json!(null)));
+        assert!(!is_overflowing(OverflowAxis::Either, &serde_json::json!([1, 0])));
+    }
+
+    #[test]
+    fn test_is_width_fraction() {
+        assert!(is_width_fraction(250.0, 1000.0, 0.25, 0.0));
+        assert!(is_width_fraction(245.0, 1000.0, 0.25, 0.01));
+        assert!(!is_width_fraction(230.0, 1000.0, 0.25, 0.01));
+        assert!(!is_width_fraction(0.0, 0.0, 0.0, 0.01));
+    }
+
+    #[test]
+    fn test_stability_tracker() {
+        let mut tracker = StabilityTracker::new();
+        assert_eq!(tracker.observe("a"), 1);
+        assert_eq!(tracker.observe("a"), 2);
+        assert_eq!(tracker.observe("b"), 1);
+        assert_eq!(tracker.observe("b"), 2);
+        assert_eq!(tracker.observe("b"), 3);
+    }
+
+    #[test]
+    fn test_is_selection() {
+        assert!(is_selection(&serde_json::json!([3, 3]), 3, 3));
+        assert!(is_selection(&serde_json::json!([0, 5]), 0, 5));
+        assert!(!is_selection(&serde_json::json!([0, 5]), 0, 4));
+        assert!(!is_selection(&Value::Null, 0, 0));
+    }
+
+    #[test]
+    fn test_parse_snapshot() {
+        let value = serde_json::json!({
+            "displayed": true,
+            "enabled": false,
+            "selected": false,
+            "text": "Done",
+            "rect": {"x": 1.0, "y": 2.0, "width": 30.0, "height": 40.0},
+            "attributes": {"id": "status", "class": "ready"}
+        });
+        let snapshot = parse_snapshot(&value);
+        assert!(snapshot.present);
+        assert_eq!(snapshot.displayed, Some(true));
+        assert_eq!(snapshot.enabled, Some(false));
+        assert_eq!(snapshot.text.as_deref(), Some("Done"));
+        assert_eq!(snapshot.rect.map(|r| r.width), Some(30.0));
+        let attributes = snapshot.attributes.unwrap();
+        assert_eq!(attributes.get("class").map(|x| x.as_str()), Some("ready"));
+
+        let missing = parse_snapshot(&serde_json::json!(null));
+        assert!(!missing.present);
+        assert!(missing.attributes.is_none());
+    }
+
+    #[test]
+    fn test_is_carousel_at() {
+        assert!(is_carousel_at(2.0, 2, 0.0));
+        assert!(is_carousel_at(1.95, 2, 0.1));
+        assert!(!is_carousel_at(1.5, 2, 0.1));
+        assert!(!is_carousel_at(2.0, 1, 0.1));
+    }
+
+    #[test]
+    fn test_axis_gap() {
+        assert_eq!(axis_gap(0.0, 10.0, 15.0, 10.0), 5.0);
+        assert_eq!(axis_gap(15.0, 10.0, 0.0, 10.0), 5.0);
+        assert_eq!(axis_gap(0.0, 10.0, 10.0, 10.0), 0.0);
+        assert_eq!(axis_gap(0.0, 10.0, 5.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color("rgb(255, 128, 0)"), Some((255, 128, 0, 255)));
+        assert_eq!(parse_color(" rgba(0, 0, 0, 0.5) "), Some((0, 0, 0, 128)));
+        assert_eq!(parse_color("rgb(10 20 30 / 0%)"), Some((10, 20, 30, 0)));
+        assert_eq!(parse_color("rgb(100%, 0%, 50%)"), Some((255, 0, 128, 255)));
+        assert_eq!(parse_color("#FFF"), Some((255, 255, 255, 255)));
+        assert_eq!(parse_color("#11223344"), Some((17, 34, 51, 68)));
+        assert_eq!(parse_color("Red"), Some((255, 0, 0, 255)));
+        assert_eq!(parse_color("transparent"), Some((0, 0, 0, 0)));
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("rgb(1, 2)"), None);
+        assert_eq!(parse_color("hsl(0, 100%, 50%)"), None);
+    }
+
+    #[test]
+    fn test_is_within() {
+        let now = SystemTime::now();
+        let window = Duration::from_secs(10);
+        assert!(is_within(now - Duration::from_secs(5), now, window));
+        assert!(is_within(now + Duration::from_secs(5), now, window));
+        assert!(is_within(now - window, now, window));
+        assert!(!is_within(now - Duration::from_secs(11), now, window));
+        assert!(!is_within(now + Duration::from_secs(11), now, window));
+    }
+
+    #[test]
+    fn test_is_rfc3339() {
+        assert!(is_rfc3339("2021-03-04T05:06:07Z"));
+        assert!(is_rfc3339("2021-03-04T05:06:07.123+01:00"));
+        assert!(is_rfc3339("2021-03-04 05:06:07-08:00"));
+        assert!(!is_rfc3339("2021-03-04T05:06:07"));
+        assert!(!is_rfc3339("2021-13-04T05:06:07Z"));
+        assert!(!is_rfc3339("2021-03-04T05:06:07.Z"));
+        assert!(!is_rfc3339("not a date at all!!!"));
+    }
+
+    #[test]
+    fn test_normalize_whitespace() {
+        assert_eq!(normalize_whitespace("  Hello\n\t  world\u{a0}! "), "Hello world !");
+        assert_eq!(normalize_whitespace("\n\n"), "");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("45%"), Some(45.0));
+        assert_eq!(parse_number("1,234 items"), Some(1234.0));
+        assert_eq!(parse_number("-2.5"), Some(-2.5));
+        assert_eq!(parse_number("none"), None);
+        assert_eq!(parse_number("Items - 45"), Some(45.0));
+        assert_eq!(parse_number("Step 3 of 10"), Some(3.0));
+        assert_eq!(parse_number("v1.2.3"), Some(1.2));
+        assert_eq!(parse_number("Total: 1,234,567.89 EUR"), Some(1234567.89));
+        assert_eq!(parse_number("3, 4 and 5"), Some(3.0));
+        assert_eq!(parse_number("Page 1."), Some(1.0));
+    }
+
+    #[test]
+    fn test_error_policy() {
+        let err = || Err(WebDriverError::CustomError("oops".to_string()));
+        assert!(handle_errors(Ok(true), ErrorPolicy::TreatAs(false)).unwrap());
+        assert!(!handle_errors(err(), true).unwrap());
+        assert!(handle_errors(err(), false).is_err());
+        assert!(handle_errors(err(), ErrorPolicy::TreatAs(true)).unwrap());
+        assert!(handle_missing(err(), ErrorPolicy::TreatAs(true), false).is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use crate::observer::notify_observer;
+use crate::poller::{default_poller, session_ticker};
+use crate::{conditions, DriverPredicate, ElementPoller, ElementPollerTicker, WaitError};
+use std::time::{Duration, Instant};
+use stringmatch::Needle;
+use thirtyfour::error::WebDriverError;
+use thirtyfour::prelude::{WebDriver, WebDriverResult};
+use thirtyfour::WebDriverSession;
+
+/// High-level interface for waiting until the driver (i.e. the current page) satisfies one or
+/// more conditions, such as the page title or URL.
+#[derive(Debug, Clone)]
+pub struct DriverWaiter<'a> {
+    session: &'a WebDriverSession,
+    poller: ElementPoller,
+    message: String,
+    ignore_errors: bool,
+}
+
+impl<'a> DriverWaiter<'a> {
+    fn new(session: &'a WebDriverSession, poller: ElementPoller) -> Self {
+        Self {
+            session,
+            poller,
+            message: String::new(),
+            ignore_errors: true,
+        }
+    }
+
+    /// Use the specified ElementPoller for this DriverWaiter.
+    /// This will not affect the default ElementPoller used for other waits.
+    pub fn with_poller(mut self, poller: ElementPoller) -> Self {
+        self.poller = poller;
+        self
+    }
+
+    /// Provide a human-readable error message to be returned in the case of timeout.
+    pub fn error(mut self, message: &str) -> Self {
+        self.message = message.to_string();
+        self
+    }
+
+    /// By default a waiter will ignore any errors that occur while polling for the desired
+    /// condition(s), e.g. while the page is still navigating. However, this behaviour can be
+    /// modified so that the waiter will return early if an error is returned from thirtyfour.
+    pub fn ignore_errors(mut self, ignore: bool) -> Self {
+        self.ignore_errors = ignore;
+        self
+    }
+
+    /// Force this DriverWaiter to wait for the specified timeout, polling once
+    /// after each interval. This will override the poller for this
+    /// DriverWaiter only.
+    pub fn wait(self, timeout: Duration, interval: Duration) -> Self {
+        self.with_poller(ElementPoller::TimeoutWithInterval(timeout, interval))
+    }
+
+    async fn run_poller(&self, conditions: Vec<DriverPredicate>) -> WebDriverResult<()> {
+        let mut ticker = session_ticker(self.session, &self.poller)?;
+        let result = self.poll_conditions(&conditions, &mut ticker).await;
+        let description = if self.message.is_empty() {
+            "DriverWaiter"
+        } else {
+            &self.message
+        };
+        notify_observer(description, &ticker, result.is_ok());
+        result
+    }
+
+    async fn poll_conditions(
+        &self,
+        conditions: &[DriverPredicate],
+        ticker: &mut ElementPollerTicker,
+    ) -> WebDriverResult<()> {
+        loop {
+            let check_start = Instant::now();
+            let mut conditions_met = true;
+            for f in conditions {
+                if !f(self.session).await? {
+                    conditions_met = false;
+                    break;
+                }
+            }
+
+            if conditions_met {
+                return Ok(());
+            }
+
+            ticker.record_check_duration(check_start.elapsed());
+            if !ticker.tick().await {
+                return Err(self.timeout(ticker));
+            }
+        }
+    }
+
+    fn timeout(&self, ticker: &ElementPollerTicker) -> WebDriverError {
+        WaitError::new(&self.message, ticker.attempts(), ticker.elapsed()).into()
+    }
+
+    pub async fn condition(&self, f: DriverPredicate) -> WebDriverResult<()> {
+        self.run_poller(vec![f]).await
+    }
+
+    pub async fn conditions(&self, conditions: Vec<DriverPredicate>) -> WebDriverResult<()> {
+        self.run_poller(conditions).await
+    }
+
+    pub async fn has_title<N>(&self, title: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::driver_title(title, ignore_errors)).await
+    }
+
+    pub async fn has_url<N>(&self, url: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::driver_url(url, ignore_errors)).await
+    }
+
+    /// Wait until no new network requests have completed for the specified duration.
+    /// See `conditions::driver_network_idle()` for the limits of this heuristic.
+    pub async fn network_idle(&self, quiet: Duration) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::driver_network_idle(quiet, ignore_errors)).await
+    }
+}
+
+/// Trait for enabling the DriverWaiter interface.
+pub trait DriverWaitable {
+    fn wait_until(&self) -> DriverWaiter;
+}
+
+impl DriverWaitable for WebDriver {
+    /// Return a DriverWaiter instance for waiting on page-level conditions.
+    fn wait_until(&self) -> DriverWaiter {
+        let poller = default_poller(&self.session);
+        DriverWaiter::new(&self.session, poller)
+    }
+}
+
+#[cfg(test)]
+/// This function checks if the public async methods implement Send. It is not intended to be executed.
+async fn _test_is_send() -> WebDriverResult<()> {
+    use thirtyfour::prelude::*;
+
+    // Helper methods
+    fn is_send_val<T: Send>(_val: &T) {}
+
+    // Pre values
+    let caps = DesiredCapabilities::chrome();
+    let driver = WebDriver::new("http://localhost:4444", &caps).await?;
+
+    // DriverWaiter
+    is_send_val(&driver.wait_until().has_title("title"));
+    is_send_val(&driver.wait_until().has_url("https://example.com"));
+
+    Ok(())
+}
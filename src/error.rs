@@ -0,0 +1,93 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+use thirtyfour::error::WebDriverError;
+
+/// Error describing a wait that timed out before its condition(s) were met.
+///
+/// This includes the number of polling attempts and the elapsed time, as well as the last
+/// underlying WebDriverError (if any) that made a poll attempt fail, which is available via
+/// `source()`. It converts into `WebDriverError::Timeout` so that it can be used with `?` in
+/// functions returning `WebDriverResult`.
+///
+/// NOTE: `WebDriverError::Timeout` can only hold a message, so the conversion only preserves
+/// the text of this error (see `From<WaitError> for WebDriverError`). Most waiters and queries
+/// in this crate return `WebDriverResult`, so their timeouts reach callers (and error
+/// reporters such as `anyhow` or `color_eyre`) as a `WebDriverError::Timeout` whose message
+/// includes the attempts, elapsed time and source, but without the structured fields or a
+/// `source()` chain. Use `ElementWaiter::try_condition()` or `try_conditions()` to get the
+/// WaitError itself.
+#[derive(Debug)]
+pub struct WaitError {
+    pub message: String,
+    pub attempts: u32,
+    pub elapsed: Duration,
+    pub source: Option<WebDriverError>,
+}
+
+impl WaitError {
+    pub fn new(message: &str, attempts: u32, elapsed: Duration) -> Self {
+        Self {
+            message: message.to_string(),
+            attempts,
+            elapsed,
+            source: None,
+        }
+    }
+
+    /// Attach the underlying error that caused the final attempt to fail.
+    pub fn with_source(mut self, source: WebDriverError) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl fmt::Display for WaitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.message.is_empty() {
+            write!(f, "{}: ", self.message)?;
+        }
+        write!(f, "timed out after {} attempt(s) in {:?}", self.attempts, self.elapsed)
+    }
+}
+
+impl Error for WaitError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|e| e as &(dyn Error + 'static))
+    }
+}
+
+/// Convert into `WebDriverError::Timeout`, with the `Display` text of the WaitError followed
+/// by the message of the source (if any) as its message, i.e.
+/// `"<message>: timed out after <n> attempt(s) in <elapsed>: <source>"`. The message starts
+/// with the message set on the waiter or query, as the plain timeout message used to. Only
+/// this text is preserved; the structured fields and the source itself are dropped.
+impl From<WaitError> for WebDriverError {
+    fn from(err: WaitError) -> Self {
+        match &err.source {
+            Some(source) => WebDriverError::Timeout(format!("{}: {}", err, source)),
+            None => WebDriverError::Timeout(err.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_error_source_chain() {
+        let err = WaitError::new("Button never appeared", 3, Duration::from_secs(1))
+            .with_source(WebDriverError::CustomError(String::from("stale")));
+        assert!(err.to_string().starts_with("Button never appeared: timed out after 3 attempt(s)"));
+        assert!(err.source().is_some());
+
+        match WebDriverError::from(err) {
+            WebDriverError::Timeout(x) => {
+                assert!(x.starts_with("Button never appeared: timed out after 3 attempt(s)"));
+                assert!(x.ends_with(": stale"));
+            }
+            e => panic!("unexpected error: {}", e),
+        }
+    }
+}
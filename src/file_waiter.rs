@@ -0,0 +1,260 @@
+use crate::observer::notify_observer;
+use crate::poller::{default_poller, is_no_wait_forced};
+use crate::{ElementPoller, ElementPollerTicker, WaitError};
+use futures::Future;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use thirtyfour::error::{WebDriverError, WebDriverResult};
+use thirtyfour::WebDriverSession;
+
+/// Match the specified file name against a simple wildcard pattern, where `*` matches any
+/// sequence of characters and `?` matches any single character.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+
+    // matches[j] is true if the pattern so far matches the first j characters of the name.
+    let mut matches = vec![false; name.len() + 1];
+    matches[0] = true;
+    for p in pattern {
+        let mut next = vec![false; name.len() + 1];
+        match p {
+            '*' => {
+                let mut any = false;
+                for j in 0..=name.len() {
+                    any = any || matches[j];
+                    next[j] = any;
+                }
+            }
+            _ => {
+                for j in 1..=name.len() {
+                    next[j] = matches[j - 1] && (p == '?' || p == name[j - 1]);
+                }
+            }
+        }
+        matches = next;
+    }
+    matches[name.len()]
+}
+
+/// Get the size of the specified file, or None if it does not exist (yet).
+#[cfg(feature = "tokio-runtime")]
+async fn file_size(path: &Path) -> Option<u64> {
+    match tokio::fs::metadata(path).await {
+        Ok(x) if x.is_file() => Some(x.len()),
+        _ => None,
+    }
+}
+
+/// Get the size of the specified file, or None if it does not exist (yet).
+#[cfg(feature = "async-std-runtime")]
+async fn file_size(path: &Path) -> Option<u64> {
+    match async_std::fs::metadata(path).await {
+        Ok(x) if x.is_file() => Some(x.len()),
+        _ => None,
+    }
+}
+
+/// Get the paths of all entries directly inside the specified directory, or an empty Vec if it
+/// cannot be read.
+#[cfg(feature = "tokio-runtime")]
+async fn dir_entries(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Ok(mut entries) = tokio::fs::read_dir(dir).await {
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            paths.push(entry.path());
+        }
+    }
+    paths
+}
+
+/// Get the paths of all entries directly inside the specified directory, or an empty Vec if it
+/// cannot be read.
+#[cfg(feature = "async-std-runtime")]
+async fn dir_entries(dir: &Path) -> Vec<PathBuf> {
+    use futures::StreamExt;
+
+    let mut paths = Vec::new();
+    if let Ok(mut entries) = async_std::fs::read_dir(dir).await {
+        while let Some(Ok(entry)) = entries.next().await {
+            paths.push(entry.path().into());
+        }
+    }
+    paths
+}
+
+/// High-level interface for waiting until files appear on the local filesystem, e.g. to wait
+/// for a download triggered by the browser to complete.
+///
+/// NOTE: This polls the filesystem of the machine running the tests, so it only works when the
+/// browser is running on the same machine and downloads files locally. It will not see files
+/// downloaded by a remote browser (e.g. via Selenium Grid or a browser running in Docker),
+/// unless the download directory is shared with the local machine.
+///
+/// This requires the `fs` feature.
+#[derive(Debug, Clone)]
+pub struct FileWaiter {
+    poller: ElementPoller,
+    message: String,
+    force_no_wait: bool,
+}
+
+impl FileWaiter {
+    pub fn new(poller: ElementPoller) -> Self {
+        Self {
+            poller,
+            message: String::new(),
+            force_no_wait: false,
+        }
+    }
+
+    /// Create a FileWaiter using the default poller of the specified session. Unlike
+    /// `new()`, this also honours `DefaultPollerConfig::set_force_no_wait()` for the session,
+    /// even if another poller is set via `with_poller()` or `wait()`.
+    pub fn for_session(session: &WebDriverSession) -> Self {
+        let mut waiter = Self::new(default_poller(session));
+        waiter.force_no_wait = is_no_wait_forced(session);
+        waiter
+    }
+
+    /// Use the specified ElementPoller for this FileWaiter.
+    pub fn with_poller(mut self, poller: ElementPoller) -> Self {
+        self.poller = poller;
+        self
+    }
+
+    /// Provide a human-readable error message to be returned in the case of timeout.
+    pub fn error(mut self, message: &str) -> Self {
+        self.message = message.to_string();
+        self
+    }
+
+    /// Force this FileWaiter to wait for the specified timeout, polling once
+    /// after each interval.
+    pub fn wait(self, timeout: Duration, interval: Duration) -> Self {
+        self.with_poller(ElementPoller::TimeoutWithInterval(timeout, interval))
+    }
+
+    /// Create a ticker for this FileWaiter's poller, or for `NoWait` if it is being forced.
+    fn new_ticker(&self) -> WebDriverResult<ElementPollerTicker> {
+        match self.force_no_wait {
+            true => ElementPollerTicker::try_new(ElementPoller::NoWait),
+            false => ElementPollerTicker::try_new(self.poller.clone()),
+        }
+    }
+
+    fn timeout(&self, ticker: &ElementPollerTicker) -> WebDriverError {
+        WaitError::new(&self.message, ticker.attempts(), ticker.elapsed()).into()
+    }
+
+    /// Poll the specified check until it returns a value, and return it. The observer (if
+    /// any) is notified of the result.
+    async fn poll<T, F, Fut>(&self, check: F) -> WebDriverResult<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Option<T>>,
+    {
+        let mut ticker = self.new_ticker()?;
+        let result = loop {
+            let check_start = Instant::now();
+            if let Some(x) = check().await {
+                break Ok(x);
+            }
+
+            ticker.record_check_duration(check_start.elapsed());
+            if !ticker.tick().await {
+                break Err(self.timeout(&ticker));
+            }
+        };
+        let description = if self.message.is_empty() {
+            "FileWaiter"
+        } else {
+            &self.message
+        };
+        notify_observer(description, &ticker, result.is_ok());
+        result
+    }
+
+    /// Wait until the specified file exists.
+    pub async fn file_exists<P>(&self, path: P) -> WebDriverResult<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        self.poll(|| async move { file_size(path).await.map(|_| ()) }).await
+    }
+
+    /// Wait until the specified file exists and its size has not changed between two
+    /// consecutive polls. Note that this depends on the poll interval being long enough for
+    /// the size of an in-progress download to change between polls.
+    pub async fn file_size_stable<P>(&self, path: P) -> WebDriverResult<()>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref();
+        let last_size = &Mutex::new(None);
+        self.poll(|| async move {
+            let size = file_size(path).await;
+            let last = std::mem::replace(&mut *last_size.lock().unwrap(), size);
+            match size.is_some() && size == last {
+                true => Some(()),
+                false => None,
+            }
+        })
+        .await
+    }
+
+    /// Wait until a file in the specified directory has a name matching the specified pattern,
+    /// and return its path. The pattern supports `*` (any sequence of characters) and `?`
+    /// (any single character) wildcards, e.g. `report-*.pdf`. Only files directly inside the
+    /// directory are considered.
+    ///
+    /// Browsers typically download to a temporary file (e.g. `*.crdownload` or `*.part`) which
+    /// is renamed when the download is complete, so make sure the pattern does not match it.
+    pub async fn file_matches_glob<P>(&self, dir: P, pattern: &str) -> WebDriverResult<PathBuf>
+    where
+        P: AsRef<Path>,
+    {
+        let dir = dir.as_ref();
+        self.poll(|| async move {
+            for path in dir_entries(dir).await {
+                let is_match = path
+                    .file_name()
+                    .and_then(|x| x.to_str())
+                    .is_some_and(|x| wildcard_match(pattern, x));
+                if is_match && file_size(&path).await.is_some() {
+                    return Some(path);
+                }
+            }
+            None
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("report-*.pdf", "report-2021.pdf"));
+        assert!(wildcard_match("report-*.pdf", "report-.pdf"));
+        assert!(wildcard_match("file?.txt", "file1.txt"));
+        assert!(wildcard_match("*", "anything"));
+        assert!(!wildcard_match("report-*.pdf", "report-2021.pdf.crdownload"));
+        assert!(!wildcard_match("file?.txt", "file.txt"));
+    }
+
+    #[test]
+    fn test_force_no_wait_overrides_poller() {
+        let waiter = FileWaiter::new(ElementPoller::NoWait)
+            .wait(Duration::from_secs(60), Duration::from_secs(1));
+        let forced = FileWaiter {
+            force_no_wait: true,
+            ..waiter
+        };
+        assert!(!futures::executor::block_on(forced.new_ticker().unwrap().tick()));
+    }
+}
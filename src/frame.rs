@@ -0,0 +1,142 @@
+use futures::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use thirtyfour::prelude::WebDriverResult;
+use thirtyfour::{WebDriverCommands, WebDriverSession, WebElement};
+
+/// The number of scopes dropped without being finished. See `FrameScope::leaked()`.
+static LEAKED_SCOPES: AtomicUsize = AtomicUsize::new(0);
+
+/// Tracks whether a scope has been finished. If it is dropped without being finished, e.g.
+/// because the future using it was cancelled, it is counted as leaked (and logged as a warning
+/// with the `log` feature enabled).
+#[derive(Debug)]
+struct ScopeTracker {
+    finished: bool,
+}
+
+impl ScopeTracker {
+    fn new() -> Self {
+        Self {
+            finished: false,
+        }
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+impl Drop for ScopeTracker {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        LEAKED_SCOPES.fetch_add(1, Ordering::SeqCst);
+        #[cfg(feature = "log")]
+        {
+            log::warn!(
+                "FrameScope dropped without calling finish(), \
+                 so the session is still switched into the frame"
+            );
+        }
+    }
+}
+
+/// Guard that switches the session into an iframe, and switches back to the default content
+/// when `finish()` is called. Prefer `FrameScope::run()`, which always calls `finish()` once
+/// the closure completes, even if it returns an error.
+///
+/// ```ignore
+/// let frame = driver.query(By::Css("iframe#editor")).first().await?;
+/// let scope = FrameScope::enter(&frame).await?;
+/// let result = driver.query(By::Id("content")).first().await;
+/// scope.finish().await?;
+/// ```
+///
+/// NOTE: Switching back requires a WebDriver request, and `Drop` cannot run async code, so the
+/// context is only restored by `finish()`. If the guard is dropped without calling `finish()`,
+/// e.g. because the future using it was cancelled by a timeout, the session stays switched
+/// into the frame and subsequent queries will search inside it. Such scopes are counted (see
+/// `FrameScope::leaked()`), and with the `log` feature enabled this is logged as a warning.
+/// To be safe after a cancellation, call `driver.switch_to().default_content()` before the
+/// next query.
+#[derive(Debug)]
+#[must_use = "the frame is only exited when finish() is called"]
+pub struct FrameScope<'a> {
+    session: &'a WebDriverSession,
+    tracker: ScopeTracker,
+}
+
+impl<'a> FrameScope<'a> {
+    /// Switch the session into the specified iframe element.
+    pub async fn enter(frame: &WebElement<'a>) -> WebDriverResult<FrameScope<'a>> {
+        let session = frame.session;
+        frame.clone().enter_frame().await?;
+        Ok(FrameScope {
+            session,
+            tracker: ScopeTracker::new(),
+        })
+    }
+
+    /// Switch the session into the specified iframe element, run the specified closure, and
+    /// then switch back to the default content, whether or not the closure succeeded:
+    ///
+    /// ```ignore
+    /// let frame = driver.query(By::Css("iframe#editor")).first().await?;
+    /// let text = FrameScope::run(&frame, || async {
+    ///     driver.query(By::Id("content")).first().await?.text().await
+    /// })
+    /// .await?;
+    /// ```
+    ///
+    /// Returns the error from the closure if it failed, and otherwise any error from switching
+    /// back. If the returned future is cancelled, the scope is leaked as for `enter()`.
+    pub async fn run<F, Fut, T>(frame: &WebElement<'a>, f: F) -> WebDriverResult<T>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = WebDriverResult<T>>,
+    {
+        let scope = Self::enter(frame).await?;
+        let result = f().await;
+        let finished = scope.finish().await;
+        let value = result?;
+        finished.map(|_| value)
+    }
+
+    /// Switch the session back to the default content, i.e. the top-level document.
+    pub async fn finish(mut self) -> WebDriverResult<()> {
+        let result = self.session.switch_to().default_content().await;
+        self.tracker.finish();
+        result
+    }
+
+    /// Get the number of FrameScopes that have been dropped without calling `finish()`, e.g.
+    /// because the future using them was cancelled. Test suites can check this during
+    /// teardown to detect tests that may have left a session switched into a frame.
+    pub fn leaked() -> usize {
+        LEAKED_SCOPES.load(Ordering::SeqCst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    #[test]
+    fn test_cancelled_scope_is_leaked() {
+        let before = FrameScope::leaked();
+        let mut tracker = ScopeTracker::new();
+        tracker.finish();
+        drop(tracker);
+        assert_eq!(FrameScope::leaked(), before);
+
+        // Cancel a future holding a scope by dropping it while it is pending.
+        let cancelled = async {
+            let _tracker = ScopeTracker::new();
+            futures::future::pending::<()>().await;
+        };
+        assert!(cancelled.now_or_never().is_none());
+        assert_eq!(FrameScope::leaked(), before + 1);
+    }
+}
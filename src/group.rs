@@ -0,0 +1,145 @@
+use futures::future::{select, Either};
+use futures::stream::FuturesUnordered;
+use futures::{Future, StreamExt};
+use std::pin::Pin;
+use std::time::Duration;
+use thirtyfour::error::WebDriverError;
+use thirtyfour::prelude::WebDriverResult;
+use thirtyfour::support::sleep;
+
+type GroupFuture<'a> = Pin<Box<dyn Future<Output = WebDriverResult<()>> + Send + 'a>>;
+
+/// A group of independent queries and waits that are driven concurrently, for cases such as
+/// a page-load check that requires several elements at once. The group completes when all of
+/// them have completed, or fails as soon as any one of them fails.
+///
+/// ```ignore
+/// QueryGroup::new(Duration::from_secs(10))
+///     .add("header", driver.query(By::Id("header")).first())
+///     .add("results", driver.query(By::Css("li.result")).count_matches(|n| n == 3))
+///     .add("spinner", driver.query(By::Css(".spinner")).none())
+///     .run()
+///     .await?;
+/// ```
+///
+/// Each query still uses its own poller, so its own timeout applies as usual. Because they
+/// run concurrently, the group takes as long as the slowest query rather than the sum of all
+/// of them. The group timeout passed to `new()` is a shared deadline on top of that: if any
+/// query is still pending when it elapses, the group fails even if that query's own timeout
+/// has not been reached yet. The group timeout is required, so that a group containing a
+/// query that never completes (e.g. a custom future, or a wait with a `NumTriesWithInterval`
+/// poller and slow checks) still has an upper bound.
+///
+/// The values returned by the queries are discarded, so the group is intended for checks
+/// rather than for fetching elements. If a query fails with a Timeout error, the group returns
+/// a Timeout error naming the failed query and the queries that were still pending. Any other
+/// error is returned unchanged. The remaining queries are dropped in either case.
+#[must_use = "the queries only run when run() is awaited"]
+pub struct QueryGroup<'a> {
+    queries: Vec<(String, GroupFuture<'a>)>,
+    timeout: Duration,
+}
+
+impl<'a> QueryGroup<'a> {
+    /// Create a new, empty QueryGroup with the specified shared deadline for the whole group.
+    /// See the `QueryGroup` documentation.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            queries: Vec::new(),
+            timeout,
+        }
+    }
+
+    /// Add a query or wait to the group. The description is used in error messages.
+    pub fn add<F, T>(mut self, description: &str, query: F) -> Self
+    where
+        F: Future<Output = WebDriverResult<T>> + Send + 'a,
+    {
+        let query = async move { query.await.map(|_| ()) };
+        self.queries.push((description.to_string(), Box::pin(query)));
+        self
+    }
+
+    /// Run all queries concurrently, returning when all of them have completed, or as soon
+    /// as any one of them fails or the shared deadline elapses.
+    pub async fn run(self) -> WebDriverResult<()> {
+        let mut descriptions = Vec::with_capacity(self.queries.len());
+        let mut queries = FuturesUnordered::new();
+        for (index, (description, query)) in self.queries.into_iter().enumerate() {
+            descriptions.push(description);
+            queries.push(async move { (index, query.await) });
+        }
+        let mut completed = vec![false; descriptions.len()];
+
+        let mut deadline = Box::pin(sleep(self.timeout));
+
+        loop {
+            match select(queries.next(), deadline.as_mut()).await {
+                Either::Left((None, _)) => return Ok(()),
+                Either::Left((Some((index, Ok(()))), _)) => completed[index] = true,
+                Either::Left((Some((index, Err(WebDriverError::Timeout(message)))), _)) => {
+                    completed[index] = true;
+                    return Err(WebDriverError::Timeout(format!(
+                        "Query group failed because '{}' timed out: {} (still pending: {})",
+                        descriptions[index],
+                        message,
+                        pending_summary(&descriptions, &completed)
+                    )));
+                }
+                Either::Left((Some((_, Err(e))), _)) => return Err(e),
+                Either::Right(_) => {
+                    return Err(WebDriverError::Timeout(format!(
+                        "Query group timed out after {:?} (still pending: {})",
+                        self.timeout,
+                        pending_summary(&descriptions, &completed)
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// Get a comma-separated list of the descriptions of the queries that have not completed.
+fn pending_summary(descriptions: &[String], completed: &[bool]) -> String {
+    let pending: Vec<String> = descriptions
+        .iter()
+        .zip(completed)
+        .filter(|(_, done)| !**done)
+        .map(|(description, _)| format!("'{}'", description))
+        .collect();
+    if pending.is_empty() {
+        String::from("none")
+    } else {
+        pending.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+    use futures::future::{pending, ready};
+
+    #[test]
+    fn test_all_complete() {
+        let group = QueryGroup::new(Duration::from_secs(10))
+            .add("first", ready(Ok::<_, WebDriverError>(1)))
+            .add("second", ready(Ok::<_, WebDriverError>("two")));
+        assert!(block_on(group.run()).is_ok());
+    }
+
+    #[test]
+    fn test_timeout_fails_fast() {
+        let group = QueryGroup::new(Duration::from_secs(10))
+            .add("done", ready(Ok::<_, WebDriverError>(())))
+            .add("stuck", pending::<WebDriverResult<()>>())
+            .add("failed", ready(Err::<(), _>(WebDriverError::Timeout("gave up".to_string()))));
+        match block_on(group.run()) {
+            Err(WebDriverError::Timeout(message)) => {
+                assert!(message.contains("'failed' timed out: gave up"));
+                assert!(message.contains("'stuck'"));
+            }
+            x => panic!("unexpected result: {:?}", x),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use crate::{WaitEvent, WaitObserver};
+
+/// Get the specified percentile (0-100) from the sorted durations, using the nearest-rank
+/// method. Returns None if there are no durations.
+fn percentile(sorted: &[Duration], percentile: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.max(1).min(sorted.len()) - 1])
+}
+
+/// WaitObserver that records the duration of every query and wait, grouped by description,
+/// so that percentiles can be reported at the end of a test run. This is useful for
+/// identifying the slowest selectors and conditions in a suite.
+///
+/// ```ignore
+/// let histogram = Arc::new(HistogramObserver::new());
+/// set_wait_observer(histogram.clone());
+///
+/// // Run the tests...
+///
+/// println!("{}", histogram.report());
+/// ```
+///
+/// Every duration is kept, so memory usage grows with the number of waits. Give queries and
+/// waiters a description (via `desc()` or `error()`) so that they are grouped meaningfully.
+#[derive(Debug, Default)]
+pub struct HistogramObserver {
+    durations: Mutex<HashMap<String, Vec<Duration>>>,
+}
+
+impl HistogramObserver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the specified percentile (0-100) of the durations recorded for the specified
+    /// description, or None if nothing has been recorded for it.
+    pub fn percentile(&self, description: &str, p: f64) -> Option<Duration> {
+        let durations = self.durations.lock().unwrap();
+        let mut sorted = durations.get(description)?.clone();
+        sorted.sort();
+        percentile(&sorted, p)
+    }
+
+    /// Get a human-readable report of the number of waits and the p50, p90, p99 and maximum
+    /// durations for each description, slowest (by p90) first.
+    pub fn report(&self) -> String {
+        let durations = self.durations.lock().unwrap();
+        let mut rows: Vec<(&str, usize, [Duration; 4])> = durations
+            .iter()
+            .map(|(description, durations)| {
+                let mut sorted = durations.clone();
+                sorted.sort();
+                let stats =
+                    [50.0, 90.0, 99.0, 100.0].map(|p| percentile(&sorted, p).unwrap_or_default());
+                (description.as_str(), sorted.len(), stats)
+            })
+            .collect();
+        rows.sort_by(|a, b| b.2[1].cmp(&a.2[1]));
+
+        rows.iter()
+            .map(|(description, count, [p50, p90, p99, max])| {
+                format!(
+                    "{}: n={} p50={:?} p90={:?} p99={:?} max={:?}\n",
+                    description, count, p50, p90, p99, max
+                )
+            })
+            .collect()
+    }
+
+    /// Remove all recorded durations.
+    pub fn clear(&self) {
+        self.durations.lock().unwrap().clear();
+    }
+}
+
+impl WaitObserver for HistogramObserver {
+    fn on_wait(&self, event: &WaitEvent) {
+        let mut durations = self.durations.lock().unwrap();
+        durations.entry(event.description.to_string()).or_default().push(event.elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let sorted: Vec<Duration> = (1..=10).map(Duration::from_secs).collect();
+        assert_eq!(percentile(&sorted, 50.0), Some(Duration::from_secs(5)));
+        assert_eq!(percentile(&sorted, 90.0), Some(Duration::from_secs(9)));
+        assert_eq!(percentile(&sorted, 100.0), Some(Duration::from_secs(10)));
+        assert_eq!(percentile(&sorted, 0.0), Some(Duration::from_secs(1)));
+        assert_eq!(percentile(&[], 50.0), None);
+    }
+}
@@ -3,20 +3,36 @@
 //!
 //! See examples for more details.
 //!
+//! ## Async runtime
+//!
+//! By default this crate uses tokio (the `tokio-runtime` feature). To use async-std instead,
+//! disable the default features and enable the `async-std-runtime` feature. Each feature also
+//! selects the same runtime in thirtyfour. Only one runtime feature can be enabled.
+//! ```toml
+//! thirtyfour_query = { version = "*", default-features = false, features = ["async-std-runtime"] }
+//! ```
+//!
 //! ## Usage
 //!
+//! The most commonly used traits and types can be imported via the prelude:
+//! ```ignore
+//! use thirtyfour_query::prelude::*;
+//! ```
+//!
+//! Alternatively, they can be imported individually as shown below.
+//!
 //! ### ElementQuery
 //!
 //! First, import the following:
 //! ```ignore
-//! use thirtyfour_query::{ElementPoller, ElementQueryable};
+//! use thirtyfour_query::{DefaultPollerConfig, ElementPoller, ElementQueryable};
 //! ```
 //!
 //! Next, set the default polling behaviour:
 //! ```rust
 //! # use thirtyfour::prelude::*;
 //! # use thirtyfour::support::block_on;
-//! # use thirtyfour_query::{ElementPoller, ElementQueryable};
+//! # use thirtyfour_query::{DefaultPollerConfig, ElementPoller, ElementQueryable};
 //! # use std::time::Duration;
 //! #
 //! # fn main() -> WebDriverResult<()> {
@@ -27,21 +43,30 @@
 //! driver.set_implicit_wait_timeout(Duration::new(0, 0)).await?;
 //!
 //! let poller = ElementPoller::TimeoutWithInterval(Duration::new(10, 0), Duration::from_millis(500));
-//! driver.config_mut().set("ElementPoller", poller)?;
+//! driver.set_default_poller(poller)?;
 //! #         Ok(())
 //! #     })
 //! # }
 //! ```
 //!
-//! Other ElementPoller options are also available, such as NoWait and NumTriesWithInterval.
+//! Other ElementPoller options are also available, such as NoWait, NumTriesWithInterval and
+//! TimeoutWithIntervals (for a custom schedule of intervals).
+//! The `ElementPoller::QUICK`, `ElementPoller::STANDARD` and `ElementPoller::PATIENT` presets
+//! (2s/100ms, 10s/250ms and 30s/1s respectively) are suggested starting points.
 //! These can be overridden on a per-query basis if needed.
 //!
+//! Named poller profiles can also be registered, and then selected per query or waiter:
+//! ```ignore
+//! driver.set_poller_profile("network", ElementPoller::timeout_secs(60))?;
+//! driver.query(By::Id("results")).use_profile("network").first().await?;
+//! ```
+//!
 //! Now, using the query interface you can do things like:
 //!
 //! ```rust
 //! # use thirtyfour::prelude::*;
 //! # use thirtyfour::support::block_on;
-//! # use thirtyfour_query::{ElementPoller, ElementQueryable};
+//! # use thirtyfour_query::{DefaultPollerConfig, ElementPoller, ElementQueryable};
 //! # use std::time::Duration;
 //! #
 //! # fn main() -> WebDriverResult<()> {
@@ -51,7 +76,7 @@
 //! # driver.set_implicit_wait_timeout(Duration::new(0, 0)).await?;
 //! #
 //! # let poller = ElementPoller::TimeoutWithInterval(Duration::new(10, 0), Duration::from_millis(500));
-//! # driver.config_mut().set("ElementPoller", poller)?;
+//! # driver.set_default_poller(poller)?;
 //! # driver.get("http://webappdemo").await?;
 //! // This won't wait.
 //! let elem_found = driver.query(By::Id("button1")).exists().await?;
@@ -72,7 +97,7 @@
 //! ```rust
 //! # use thirtyfour::prelude::*;
 //! # use thirtyfour::support::block_on;
-//! # use thirtyfour_query::{ElementPoller, ElementQueryable, StringMatch};
+//! # use thirtyfour_query::{DefaultPollerConfig, ElementPoller, ElementQueryable, StringMatch};
 //! # use std::time::Duration;
 //! #
 //! # fn main() -> WebDriverResult<()> {
@@ -82,7 +107,7 @@
 //! # driver.set_implicit_wait_timeout(Duration::new(0, 0)).await?;
 //! #
 //! # let poller = ElementPoller::TimeoutWithInterval(Duration::new(10, 0), Duration::from_millis(500));
-//! # driver.config_mut().set("ElementPoller", poller)?;
+//! # driver.set_default_poller(poller)?;
 //! # driver.get("http://webappdemo").await?;
 //! let elem = driver.query(By::Css("thiswont.match")).with_text("testing")
 //!     .or(By::Id("button1")).with_class(StringMatch::new("pure-button").word()).and_enabled()
@@ -103,11 +128,17 @@
 //! All timeout, interval and ElementPoller details can be overridden on a per-call basis if
 //! desired. See the `ElementQuery` documentation for more details.
 //!
+//! If your application uses test ids (e.g. `data-testid` attributes), you can query by test id
+//! directly. The attribute name can be changed via the "TestIdAttribute" session config key.
+//! ```ignore
+//! let elem = driver.query_testid("submit-button").or_testid("login-button").first().await?;
+//! ```
+//!
 //! ### ElementWaiter
 //!
 //! First, import the following:
 //! ```ignore
-//! use thirtyfour_query::{ElementPoller, ElementWaitable};
+//! use thirtyfour_query::{DefaultPollerConfig, ElementPoller, ElementWaitable};
 //! ```
 //!
 //! Next, set the default polling behaviour (same as for ElementQuery - the same polling
@@ -117,7 +148,7 @@
 //! driver.set_implicit_wait_timeout(Duration::new(0, 0)).await?;
 //!
 //! let poller = ElementPoller::TimeoutWithInterval(Duration::new(20, 0), Duration::from_millis(500));
-//! driver.config_mut().set("ElementPoller", poller)?;
+//! driver.set_default_poller(poller)?;
 //! ```
 //!
 //! Now you can do things like this:
@@ -132,8 +163,30 @@
 //!
 //! And so on. See the `ElementWaiter` docs for the full list of predicates available.
 //!
+//! If you prefer not to import the `ElementWaitable` trait, the `wait()` function creates an
+//! ElementWaiter directly, with an optional error message:
+//! ```ignore
+//! thirtyfour_query::wait(&elem, "Timed out waiting for element").displayed().await?;
+//! ```
+//!
+//! To wait on a relationship between two elements, use `wait_pair()`, which passes both
+//! elements to each predicate:
+//! ```ignore
+//! thirtyfour_query::wait_pair(&tooltip, &button).are_adjacent(8.0).await?;
+//! ```
+//!
 //! ElementWaiter also allows the user of custom predicates that take a `&WebElement` argument
-//! and return a `WebDriverResult<bool>`.
+//! and return a `WebDriverResult<bool>`. The easiest way to create one is from an async fn, using
+//! `conditions::predicate()`:
+//!
+//! ```ignore
+//! async fn has_items(elem: &WebElement<'_>) -> WebDriverResult<bool> {
+//!     Ok(!elem.find_elements(By::Tag("li")).await?.is_empty())
+//! }
+//!
+//! elem.wait_until().condition(conditions::predicate(has_items)).await?;
+//! ```
+//!
 //!
 //! A range of pre-defined predicates are also supplied for convenience in the
 //! `thirtyfour_query::conditions` module.
@@ -151,13 +204,92 @@
 //!
 //! These predicates (or your own) can also be supplied as filters to `ElementQuery`.
 //!
+//! To continue after a failed wait and report all failures at the end, record timeouts in a
+//! `SoftAssertions` collector instead of returning them:
+//! ```ignore
+//! let soft = SoftAssertions::new();
+//! elem.wait_until().soft(&soft).error("Banner not shown").displayed().await?;
+//! soft.fail_if_any()?;
+//! ```
+//!
+//! ### DriverWaiter
+//!
+//! Page-level conditions such as the title or URL can be waited on via the `DriverWaitable`
+//! trait, using the same polling settings:
+//! ```ignore
+//! use thirtyfour_query::DriverWaitable;
+//!
+//! driver.wait_until().has_title("Dashboard").await?;
+//! driver.wait_until().has_url(StringMatch::new("/dashboard").partial()).await?;
+//! ```
+//!
+//! ### Concurrent queries
+//!
+//! Independent queries and waits can be driven concurrently with a `QueryGroup`, under a
+//! shared deadline. The group fails as soon as any one of them fails:
+//! ```ignore
+//! QueryGroup::new(Duration::from_secs(10))
+//!     .add("header", driver.query(By::Id("header")).first())
+//!     .add("spinner", driver.query(By::Css(".spinner")).none())
+//!     .run()
+//!     .await?;
+//! ```
+//!
+//! ### Observing waits
+//!
+//! A `WaitObserver` can be installed globally via `set_wait_observer()` to be notified of
+//! the duration and outcome of every query and wait. With the `histogram` feature enabled,
+//! `HistogramObserver` records these durations and reports percentiles per description,
+//! which is useful for finding the slowest selectors in a test suite:
+//! ```ignore
+//! let histogram = Arc::new(HistogramObserver::new());
+//! set_wait_observer(histogram.clone());
+//! // Run the tests...
+//! println!("{}", histogram.report());
+//! ```
+//!
+
+#[cfg(all(feature = "tokio-runtime", feature = "async-std-runtime"))]
+compile_error!("Only one of the `tokio-runtime` and `async-std-runtime` features can be enabled");
 
+#[cfg(not(any(feature = "tokio-runtime", feature = "async-std-runtime")))]
+compile_error!("One of the `tokio-runtime` and `async-std-runtime` features must be enabled");
+
+mod bi_waiter;
 pub mod conditions;
+mod driver_waiter;
+mod error;
+#[cfg(feature = "fs")]
+mod file_waiter;
+mod frame;
+mod group;
+#[cfg(feature = "histogram")]
+mod histogram;
+mod observer;
 mod poller;
+pub mod prelude;
 mod query;
+mod soft;
+mod spec;
+#[cfg(feature = "visual")]
+mod visual;
 mod waiter;
+pub use bi_waiter::*;
+pub use driver_waiter::*;
+pub use error::*;
+#[cfg(feature = "fs")]
+pub use file_waiter::*;
+pub use frame::*;
+pub use group::*;
+#[cfg(feature = "histogram")]
+pub use histogram::*;
+pub use observer::*;
 pub use poller::*;
 pub use query::*;
+pub use soft::*;
+pub use spec::*;
+#[cfg(feature = "visual")]
+pub use visual::screenshot_with_padding;
 pub use waiter::*;
 
 use futures::Future;
@@ -165,7 +297,7 @@ use std::pin::Pin;
 /// This is a re-export of stringmatch::StringMatch.
 pub use stringmatch::StringMatch;
 use thirtyfour::prelude::WebDriverResult;
-use thirtyfour::WebElement;
+use thirtyfour::{WebDriverSession, WebElement};
 
 /// Function signature for element predicates.
 pub type ElementPredicate = Box<
@@ -176,3 +308,24 @@ pub type ElementPredicate = Box<
         + Sync
         + 'static,
 >;
+
+/// Function signature for predicates on a pair of elements. See `BiElementWaiter`.
+pub type ElementPairPredicate = Box<
+    dyn for<'a> Fn(
+            &'a WebElement<'a>,
+            &'a WebElement<'a>,
+        ) -> Pin<Box<dyn Future<Output = WebDriverResult<bool>> + Send + 'a>>
+        + Send
+        + Sync
+        + 'static,
+>;
+
+/// Function signature for driver predicates.
+pub type DriverPredicate = Box<
+    dyn for<'a> Fn(
+            &'a WebDriverSession,
+        ) -> Pin<Box<dyn Future<Output = WebDriverResult<bool>> + Send + 'a>>
+        + Send
+        + Sync
+        + 'static,
+>;
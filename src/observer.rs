@@ -0,0 +1,55 @@
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use crate::ElementPollerTicker;
+
+/// The globally installed WaitObserver, if any.
+static OBSERVER: RwLock<Option<Arc<dyn WaitObserver>>> = RwLock::new(None);
+
+/// Details of a completed query or wait, as passed to a `WaitObserver`.
+#[derive(Debug, Clone)]
+pub struct WaitEvent<'a> {
+    /// The description of the query (see `ElementQuery::desc()`) or the error message of the
+    /// waiter (see `ElementWaiter::error()`). If neither was set, this is the list of selectors
+    /// for a query, or the name of the waiter type.
+    pub description: &'a str,
+    /// The total time spent polling.
+    pub elapsed: Duration,
+    /// The number of polling attempts completed.
+    pub attempts: u32,
+    /// Whether the query or wait succeeded, rather than timing out or returning an error.
+    pub success: bool,
+}
+
+/// Trait for observing every query and wait, e.g. to collect timing statistics across a
+/// test run. Install an observer using `set_wait_observer()`.
+pub trait WaitObserver: Send + Sync {
+    /// Called once each time a query or wait completes.
+    fn on_wait(&self, event: &WaitEvent);
+}
+
+/// Install the specified WaitObserver globally, replacing any observer that was already
+/// installed. It will be notified of all queries and waits, regardless of which WebDriver
+/// they were created from.
+pub fn set_wait_observer(observer: Arc<dyn WaitObserver>) {
+    *OBSERVER.write().unwrap() = Some(observer);
+}
+
+/// Remove the globally installed WaitObserver, if any.
+pub fn clear_wait_observer() {
+    *OBSERVER.write().unwrap() = None;
+}
+
+/// Notify the installed WaitObserver (if any) that a query or wait has completed.
+pub(crate) fn notify_observer(description: &str, ticker: &ElementPollerTicker, success: bool) {
+    let observer = match OBSERVER.read().unwrap().clone() {
+        Some(x) => x,
+        None => return,
+    };
+    observer.on_wait(&WaitEvent {
+        description,
+        elapsed: ticker.elapsed(),
+        attempts: ticker.attempts(),
+        success,
+    });
+}
@@ -1,9 +1,98 @@
+use futures::Future;
 use serde::{Deserialize, Serialize};
-use std::time::{Duration, Instant};
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use thirtyfour::error::{WebDriverError, WebDriverResult};
 use thirtyfour::support::sleep;
+use thirtyfour::{WebDriver, WebDriverSession};
+
+/// The session config key used to store the default ElementPoller.
+const ELEMENT_POLLER_KEY: &str = "ElementPoller";
+
+/// The prefix of the session config keys used to store named ElementPoller profiles.
+const ELEMENT_POLLER_PROFILE_PREFIX: &str = "ElementPoller:";
+
+/// Get the ElementPoller registered under the specified profile name, if any.
+pub(crate) fn profile_poller(session: &WebDriverSession, name: &str) -> Option<ElementPoller> {
+    session.config().get(&format!("{}{}", ELEMENT_POLLER_PROFILE_PREFIX, name)).ok()
+}
+
+/// The session config key used to force all queries and waiters to use `NoWait`.
+const FORCE_NO_WAIT_KEY: &str = "ForceNoWait";
+
+/// Return true if `NoWait` is being forced for the specified session.
+/// See `DefaultPollerConfig::set_force_no_wait()`.
+pub(crate) fn is_no_wait_forced(session: &WebDriverSession) -> bool {
+    session.config().get(FORCE_NO_WAIT_KEY).unwrap_or(false)
+}
+
+/// Get the default ElementPoller for the specified session, or `NoWait` if none is set or
+/// `NoWait` is being forced.
+pub(crate) fn default_poller(session: &WebDriverSession) -> ElementPoller {
+    if is_no_wait_forced(session) {
+        return ElementPoller::NoWait;
+    }
+    session.config().get(ELEMENT_POLLER_KEY).unwrap_or(ElementPoller::NoWait)
+}
+
+/// Create a ticker for a wait in the specified session, using `NoWait` instead of the
+/// specified poller if `NoWait` is being forced. All queries and waiters bound to a session
+/// create their tickers via this function, so that forcing `NoWait` also applies to pollers
+/// set explicitly on an individual query or waiter.
+pub(crate) fn session_ticker(
+    session: &WebDriverSession,
+    poller: &ElementPoller,
+) -> WebDriverResult<ElementPollerTicker> {
+    match is_no_wait_forced(session) {
+        true => ElementPollerTicker::try_new(ElementPoller::NoWait),
+        false => ElementPollerTicker::try_new(poller.clone()),
+    }
+}
 
 /// Parameters used to determine the polling / timeout behaviour.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+///
+/// ElementPoller can be serialized and deserialized with serde, e.g. to define poller profiles
+/// in a config file and register them via `DefaultPollerConfig::set_poller_profile()`. This
+/// is always available, since the default poller and the profiles are stored in the session
+/// config, which requires serde.
+///
+/// ElementPoller uses serde's default (externally tagged) representation: `NoWait` is the
+/// string `"NoWait"`, and every other variant is an object with the variant name as its only
+/// key. The value is the variant's single value for `SpinMaxAttempts`, and otherwise an array
+/// of its values in the order they are listed for the variant. Each `Duration` is an object
+/// with whole `secs` and the remaining `nanos`. The shape of each variant in JSON is:
+///
+/// ```json
+/// "NoWait"
+/// { "TimeoutWithInterval": [{ "secs": 10, "nanos": 0 }, { "secs": 0, "nanos": 500000000 }] }
+/// { "NumTriesWithInterval": [5, { "secs": 1, "nanos": 0 }] }
+/// {
+///     "TimeoutWithIntervalAndMinTries":
+///         [{ "secs": 10, "nanos": 0 }, { "secs": 0, "nanos": 500000000 }, 3]
+/// }
+/// { "TimeoutWithIntervals": [{ "secs": 30, "nanos": 0 }, [{ "secs": 1, "nanos": 0 }]] }
+/// { "SpinMaxAttempts": 100 }
+/// {
+///     "Adaptive": [
+///         { "secs": 10, "nanos": 0 },
+///         { "secs": 0, "nanos": 50000000 },
+///         { "secs": 1, "nanos": 0 }
+///     ]
+/// }
+/// ```
+///
+/// Or in TOML:
+///
+/// ```toml
+/// default = "NoWait"
+///
+/// [network]
+/// TimeoutWithInterval = [{ secs = 10, nanos = 0 }, { secs = 0, nanos = 500000000 }]
+/// ```
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum ElementPoller {
     /// No polling, single attempt.
     NoWait,
@@ -12,6 +101,8 @@ pub enum ElementPoller {
     /// If the previous poll attempt took longer than the interval, the next will
     /// start immediately. Once the timeout is reached, a Timeout error will be
     /// returned regardless of the actual number of polling attempts completed.
+    ///
+    /// Prefer `ElementPoller::timeout_with_interval()`, which rejects degenerate values.
     TimeoutWithInterval(Duration, Duration),
     /// Poll once every interval, up to the maximum number of polling attempts.
     /// If the previous poll attempt took longer than the interval, the next will
@@ -29,22 +120,336 @@ pub enum ElementPoller {
     /// specified timeout, then the polling attempts will continue until the
     /// timeout is reached instead.
     TimeoutWithIntervalAndMinTries(Duration, Duration, u32),
+    /// Poll up to the specified timeout, following the specified schedule of intervals.
+    /// The first interval is the minimum time between the first and second poll attempt,
+    /// the second interval between the second and third, and so on. Once the schedule
+    /// is exhausted the last interval is repeated until the timeout is reached, so the
+    /// timeout always provides the overall cap regardless of the length of the schedule.
+    /// This is useful for pages with known phases of loading, e.g. fast polling at first
+    /// followed by slower polling while waiting for a long-running request.
+    ///
+    /// The schedule must contain at least one interval, otherwise waits and queries using
+    /// this poller will fail with an error (see `ElementPollerTicker::try_new()`).
+    TimeoutWithIntervals(Duration, Vec<Duration>),
+    /// Poll up to the specified number of attempts as fast as possible, yielding to the async
+    /// runtime between attempts rather than sleeping.
+    ///
+    /// This is only appropriate for fast local drivers where the latency of each request is
+    /// negligible, because the number of attempts is the only limit on how long polling takes.
+    /// Yielding lets other tasks run between attempts, whereas a zero interval (e.g.
+    /// `TimeoutWithInterval(timeout, Duration::from_secs(0))`) never yields and effectively
+    /// busy-loops, starving other tasks on the same thread.
+    SpinMaxAttempts(u32),
+    /// Poll up to the specified timeout (the first value), adapting the interval to the
+    /// observed latency of the checks, clamped between the specified minimum (the second
+    /// value) and maximum (the third value) intervals.
+    ///
+    /// After each poll attempt, the time taken by the check (e.g. the `find_elements` request)
+    /// is fed back to the poller, and the next attempt waits for twice that long. Slow checks
+    /// therefore lead to less frequent polling, and fast checks to more responsive polling.
+    /// Where no check duration is available, the minimum interval is used.
+    ///
+    /// The minimum interval must not be greater than the maximum interval, otherwise waits and
+    /// queries using this poller will fail with an error (see `ElementPollerTicker::try_new()`).
+    Adaptive(Duration, Duration, Duration),
+}
+
+impl ElementPoller {
+    /// The interval used by `timeout_secs()` and `timeout_ms()` unless overridden.
+    const DEFAULT_INTERVAL: Duration = Duration::from_millis(500);
+
+    /// Preset for elements expected almost immediately: a timeout of 2 seconds, polling every
+    /// 100 milliseconds.
+    ///
+    /// The presets are suggested starting points rather than tuned values, so adjust them or
+    /// define your own constants if they do not suit your application, e.g.
+    /// `driver.set_default_poller(ElementPoller::STANDARD)?`.
+    pub const QUICK: ElementPoller =
+        ElementPoller::TimeoutWithInterval(Duration::from_secs(2), Duration::from_millis(100));
+
+    /// Preset for most waits: a timeout of 10 seconds, polling every 250 milliseconds.
+    /// See `QUICK` for notes on the presets.
+    pub const STANDARD: ElementPoller =
+        ElementPoller::TimeoutWithInterval(Duration::from_secs(10), Duration::from_millis(250));
+
+    /// Preset for slow operations such as uploads or reports: a timeout of 30 seconds,
+    /// polling every second. See `QUICK` for notes on the presets.
+    pub const PATIENT: ElementPoller =
+        ElementPoller::TimeoutWithInterval(Duration::from_secs(30), Duration::from_secs(1));
+
+    /// Poll up to the specified timeout, with the specified interval, returning an error if
+    /// the values are degenerate. The `TimeoutWithInterval` variant accepts them silently,
+    /// with the following behaviour:
+    ///
+    /// - An interval greater than the timeout still sleeps for the whole interval after the
+    ///   first attempt, so there is exactly one retry, which starts after the timeout has
+    ///   already passed.
+    /// - A zero timeout makes a single attempt, i.e. the same as `NoWait`.
+    /// - A zero interval polls continuously without ever sleeping or yielding to the async
+    ///   runtime. Use `SpinMaxAttempts` if that is really what you want.
+    pub fn timeout_with_interval(timeout: Duration, interval: Duration) -> WebDriverResult<Self> {
+        if timeout == Duration::new(0, 0) {
+            return Err(WebDriverError::CustomError(String::from(
+                "ElementPoller timeout must be greater than zero (use NoWait for a single attempt)",
+            )));
+        }
+        if interval == Duration::new(0, 0) {
+            return Err(WebDriverError::CustomError(String::from(
+                "ElementPoller interval must be greater than zero",
+            )));
+        }
+        if interval > timeout {
+            return Err(WebDriverError::CustomError(format!(
+                "ElementPoller interval ({:?}) must not exceed the timeout ({:?})",
+                interval, timeout
+            )));
+        }
+        Ok(ElementPoller::TimeoutWithInterval(timeout, interval))
+    }
+
+    /// Poll up to the specified timeout in seconds, with an interval of 500 milliseconds.
+    /// Use `interval_ms()` or `interval_secs()` to change the interval, e.g.
+    /// `ElementPoller::timeout_secs(20).interval_ms(250)`.
+    pub fn timeout_secs(secs: u64) -> Self {
+        ElementPoller::TimeoutWithInterval(Duration::from_secs(secs), Self::DEFAULT_INTERVAL)
+    }
+
+    /// Poll up to the specified timeout in milliseconds, with an interval of 500 milliseconds.
+    /// Use `interval_ms()` or `interval_secs()` to change the interval.
+    pub fn timeout_ms(millis: u64) -> Self {
+        ElementPoller::TimeoutWithInterval(Duration::from_millis(millis), Self::DEFAULT_INTERVAL)
+    }
+
+    /// Set the interval (in milliseconds) for this ElementPoller.
+    /// See `with_interval()` for details.
+    pub fn interval_ms(self, millis: u64) -> Self {
+        self.with_interval(Duration::from_millis(millis))
+    }
+
+    /// Set the interval (in seconds) for this ElementPoller.
+    /// See `with_interval()` for details.
+    pub fn interval_secs(self, secs: u64) -> Self {
+        self.with_interval(Duration::from_secs(secs))
+    }
+
+    /// Set the interval for this ElementPoller, keeping all other settings.
+    /// For `TimeoutWithIntervals`, this replaces the whole schedule with the single interval.
+    /// `SpinMaxAttempts` becomes `NumTriesWithInterval` with the same number of attempts, and
+    /// `Adaptive` becomes `TimeoutWithInterval` with the same timeout.
+    /// `NoWait` has no interval and is returned unchanged.
+    pub fn with_interval(self, interval: Duration) -> Self {
+        match self {
+            ElementPoller::NoWait => ElementPoller::NoWait,
+            ElementPoller::TimeoutWithInterval(timeout, _) => {
+                ElementPoller::TimeoutWithInterval(timeout, interval)
+            }
+            ElementPoller::NumTriesWithInterval(num_tries, _) => {
+                ElementPoller::NumTriesWithInterval(num_tries, interval)
+            }
+            ElementPoller::TimeoutWithIntervalAndMinTries(timeout, _, num_tries) => {
+                ElementPoller::TimeoutWithIntervalAndMinTries(timeout, interval, num_tries)
+            }
+            ElementPoller::TimeoutWithIntervals(timeout, _) => {
+                ElementPoller::TimeoutWithIntervals(timeout, vec![interval])
+            }
+            ElementPoller::SpinMaxAttempts(num_tries) => {
+                ElementPoller::NumTriesWithInterval(num_tries, interval)
+            }
+            ElementPoller::Adaptive(timeout, _, _) => {
+                ElementPoller::TimeoutWithInterval(timeout, interval)
+            }
+        }
+    }
+
+    /// Set the timeout for this ElementPoller, keeping the interval(s) and any minimum number
+    /// of attempts. `NoWait` becomes `TimeoutWithInterval` with the default interval of 500
+    /// milliseconds. `NumTriesWithInterval` becomes `TimeoutWithIntervalAndMinTries`, so the
+    /// number of attempts is still guaranteed, and likewise for `SpinMaxAttempts` using the
+    /// default interval.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        match self {
+            ElementPoller::NoWait => {
+                ElementPoller::TimeoutWithInterval(timeout, Self::DEFAULT_INTERVAL)
+            }
+            ElementPoller::TimeoutWithInterval(_, interval) => {
+                ElementPoller::TimeoutWithInterval(timeout, interval)
+            }
+            ElementPoller::NumTriesWithInterval(num_tries, interval)
+            | ElementPoller::TimeoutWithIntervalAndMinTries(_, interval, num_tries) => {
+                ElementPoller::TimeoutWithIntervalAndMinTries(timeout, interval, num_tries)
+            }
+            ElementPoller::TimeoutWithIntervals(_, intervals) => {
+                ElementPoller::TimeoutWithIntervals(timeout, intervals)
+            }
+            ElementPoller::SpinMaxAttempts(num_tries) => {
+                ElementPoller::TimeoutWithIntervalAndMinTries(
+                    timeout,
+                    Self::DEFAULT_INTERVAL,
+                    num_tries,
+                )
+            }
+            ElementPoller::Adaptive(_, min, max) => ElementPoller::Adaptive(timeout, min, max),
+        }
+    }
+}
+
+/// Trait for configuring the default ElementPoller used by all queries and waiters
+/// created from a WebDriver, unless overridden on a per-call basis.
+pub trait DefaultPollerConfig {
+    /// Set the default ElementPoller.
+    fn set_default_poller(&mut self, poller: ElementPoller) -> WebDriverResult<()>;
+
+    /// Get the default ElementPoller, or `NoWait` if none has been set or `NoWait` is being
+    /// forced via `set_force_no_wait()`.
+    fn get_default_poller(&self) -> ElementPoller;
+
+    /// Register an ElementPoller under the specified profile name, e.g. "network" for
+    /// interactions that are known to be slow. Queries and waiters can then select it via
+    /// `use_profile()`. Registering a profile with an existing name replaces it.
+    fn set_poller_profile(&mut self, name: &str, poller: ElementPoller) -> WebDriverResult<()>;
+
+    /// Get the ElementPoller registered under the specified profile name, if any.
+    fn get_poller_profile(&self, name: &str) -> Option<ElementPoller>;
+
+    /// Force all queries and waiters run in this WebDriver's session to use `NoWait`,
+    /// regardless of the default poller and of any poller set on an individual query or
+    /// waiter (e.g. via `with_poller()`, `wait()` or `use_profile()`), so that every wait
+    /// checks its condition once and fails immediately. This is a debugging aid for seeing the
+    /// raw state of the page without waiting for timeouts, and should not be left enabled in a
+    /// test suite. A `FileWaiter` only honours this if it was created via
+    /// `FileWaiter::for_session()`.
+    ///
+    /// The default implementation returns an error, since forcing `NoWait` requires storing
+    /// the flag in the session config.
+    fn set_force_no_wait(&mut self, enabled: bool) -> WebDriverResult<()> {
+        let _ = enabled;
+        Err(WebDriverError::CustomError(String::from(
+            "Forcing NoWait is not supported by this DefaultPollerConfig implementation",
+        )))
+    }
+}
+
+impl DefaultPollerConfig for WebDriver {
+    fn set_default_poller(&mut self, poller: ElementPoller) -> WebDriverResult<()> {
+        self.config_mut().set(ELEMENT_POLLER_KEY, poller)
+    }
+
+    fn get_default_poller(&self) -> ElementPoller {
+        default_poller(&self.session)
+    }
+
+    fn set_poller_profile(&mut self, name: &str, poller: ElementPoller) -> WebDriverResult<()> {
+        self.config_mut().set(&format!("{}{}", ELEMENT_POLLER_PROFILE_PREFIX, name), poller)
+    }
+
+    fn get_poller_profile(&self, name: &str) -> Option<ElementPoller> {
+        profile_poller(&self.session, name)
+    }
+
+    fn set_force_no_wait(&mut self, enabled: bool) -> WebDriverResult<()> {
+        self.config_mut().set(FORCE_NO_WAIT_KEY, enabled)
+    }
+}
+
+/// Future that returns Pending once (waking itself immediately), so that the async runtime
+/// can run other tasks before it completes.
+struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
+/// Yield to the async runtime, independent of which runtime is in use.
+async fn yield_now() {
+    YieldNow {
+        yielded: false,
+    }
+    .await
+}
+
+/// Minimal xorshift64* random number generator used for jitter, returning a value in the
+/// range [0, 1). This is not suitable for anything other than spreading out poll timings.
+#[derive(Debug, Clone)]
+struct JitterRng(u64);
+
+impl JitterRng {
+    fn new(seed: u64) -> Self {
+        // The state must never be zero, otherwise the generator only ever returns zero.
+        Self((seed ^ 0x9E37_79B9_7F4A_7C15).max(1))
+    }
+
+    fn next(&mut self) -> f64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        (x.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Callback invoked with the number of attempts so far and the elapsed time whenever a
+/// ticker is about to poll again. See `ElementWaiter::on_poll()`.
+#[derive(Clone)]
+pub(crate) struct PollCallback(Arc<dyn Fn(usize, Duration) + Send + Sync>);
+
+impl PollCallback {
+    pub(crate) fn new<F>(f: F) -> Self
+    where
+        F: Fn(usize, Duration) + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+}
+
+impl fmt::Debug for PollCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PollCallback")
+    }
 }
 
 pub struct ElementPollerTicker {
     timeout: Option<Duration>,
-    interval: Option<Duration>,
+    intervals: Vec<Duration>,
     min_tries: u32,
+    spin: bool,
+    adaptive: Option<(Duration, Duration)>,
+    last_check: Option<Duration>,
+    jitter: Option<(f64, JitterRng)>,
+    deadline: Option<Instant>,
+    on_poll: Option<PollCallback>,
     start: Instant,
     cur_tries: u32,
 }
 
 impl ElementPollerTicker {
+    /// Create a ticker for the specified poller. The poller is not validated, so a
+    /// `TimeoutWithIntervals` schedule without any intervals polls back to back until the
+    /// timeout, and an `Adaptive` poller whose minimum interval exceeds its maximum interval
+    /// always sleeps for the maximum interval. Use `try_new()` to reject such pollers instead.
     pub fn new(poller: ElementPoller) -> Self {
         let mut ticker = Self {
             timeout: None,
-            interval: None,
+            intervals: Vec::new(),
             min_tries: 0,
+            spin: false,
+            adaptive: None,
+            last_check: None,
+            jitter: None,
+            deadline: None,
+            on_poll: None,
             start: Instant::now(),
             cur_tries: 0,
         };
@@ -53,44 +458,414 @@ impl ElementPollerTicker {
             ElementPoller::NoWait => {}
             ElementPoller::TimeoutWithInterval(timeout, interval) => {
                 ticker.timeout = Some(timeout);
-                ticker.interval = Some(interval);
+                ticker.intervals = vec![interval];
             }
             ElementPoller::NumTriesWithInterval(num_tries, interval) => {
-                ticker.interval = Some(interval);
+                ticker.intervals = vec![interval];
                 ticker.min_tries = num_tries;
             }
             ElementPoller::TimeoutWithIntervalAndMinTries(timeout, interval, num_tries) => {
                 ticker.timeout = Some(timeout);
-                ticker.interval = Some(interval);
+                ticker.intervals = vec![interval];
                 ticker.min_tries = num_tries
             }
+            ElementPoller::TimeoutWithIntervals(timeout, intervals) => {
+                ticker.timeout = Some(timeout);
+                ticker.intervals = intervals;
+            }
+            ElementPoller::SpinMaxAttempts(num_tries) => {
+                ticker.min_tries = num_tries;
+                ticker.spin = true;
+            }
+            ElementPoller::Adaptive(timeout, min, max) => {
+                ticker.timeout = Some(timeout);
+                ticker.adaptive = Some((min, max));
+            }
         }
 
         ticker
     }
 
+    /// Create a ticker for the specified poller, returning an error if the poller is invalid,
+    /// i.e. a `TimeoutWithIntervals` schedule without any intervals, or an `Adaptive` poller
+    /// whose minimum interval exceeds its maximum interval.
+    pub fn try_new(poller: ElementPoller) -> WebDriverResult<Self> {
+        match &poller {
+            ElementPoller::TimeoutWithIntervals(_, intervals) if intervals.is_empty() => {
+                Err(WebDriverError::CustomError(String::from(
+                    "ElementPoller::TimeoutWithIntervals requires at least one interval",
+                )))
+            }
+            ElementPoller::Adaptive(_, min, max) if min > max => {
+                Err(WebDriverError::CustomError(String::from(
+                    "ElementPoller::Adaptive requires the minimum interval to not exceed the \
+                     maximum interval",
+                )))
+            }
+            _ => Ok(Self::new(poller)),
+        }
+    }
+
+    /// Get the minimum time that must have elapsed since the first poll started
+    /// before the next poll is due, or None if there is no interval.
+    fn minimum_elapsed(&self) -> Option<Duration> {
+        let last = *self.intervals.last()?;
+        let num_scheduled = self.intervals.len() as u32;
+
+        if self.cur_tries <= num_scheduled {
+            Some(self.intervals.iter().take(self.cur_tries as usize).sum())
+        } else {
+            // The schedule is exhausted, so keep repeating the last interval.
+            let scheduled: Duration = self.intervals.iter().sum();
+            Some(scheduled + last * (self.cur_tries - num_scheduled))
+        }
+    }
+
+    /// Record how long the last check took. For an `Adaptive` poller, this determines the
+    /// interval before the next attempt. It has no effect for other pollers.
+    pub fn record_check_duration(&mut self, duration: Duration) {
+        self.last_check = Some(duration);
+    }
+
+    /// Get the interval before the next attempt for an `Adaptive` poller, or None for other
+    /// pollers.
+    fn adaptive_interval(&self) -> Option<Duration> {
+        let (min, max) = self.adaptive?;
+        let interval = self.last_check.map_or(min, |x| x * 2);
+        Some(interval.max(min).min(max))
+    }
+
+    /// Randomize each sleep between poll attempts by up to the specified fraction of its
+    /// length, in either direction, e.g. 0.2 for a sleep of 500ms sleeps for between 400ms and
+    /// 600ms. This applies to any poller, and spreads out the requests of many concurrent
+    /// waits rather than having them poll in lockstep. The fraction is clamped between 0 and 1,
+    /// and a fraction of 0 disables jitter, reproducing the exact timing of the poller.
+    ///
+    /// The random number generator is seeded with the specified seed, for reproducible timings
+    /// in tests, or from the current time if None.
+    pub fn set_jitter(&mut self, fraction: f64, seed: Option<u64>) {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_nanos() as u64).unwrap_or(0)
+        });
+        self.jitter = Some((fraction.max(0.0).min(1.0), JitterRng::new(seed)));
+    }
+
+    /// Apply the configured jitter (if any) to the specified sleep duration.
+    fn jittered(&mut self, duration: Duration) -> Duration {
+        match &mut self.jitter {
+            Some((fraction, rng)) if *fraction > 0.0 => {
+                let factor = 1.0 + *fraction * (rng.next() * 2.0 - 1.0);
+                duration.mul_f64(factor)
+            }
+            _ => duration,
+        }
+    }
+
+    /// Stop polling at the specified absolute deadline instead of after the poller's timeout.
+    /// The poller's intervals still determine how often to poll, but its timeout and minimum
+    /// number of tries are ignored, and no sleep extends past the deadline, so the final
+    /// attempt happens at the deadline at the latest. If the deadline has already passed,
+    /// the next `tick()` returns false, so only the attempt before it is made.
+    ///
+    /// NOTE: For pollers without an interval (e.g. `NoWait`), attempts are made back to back
+    /// until the deadline.
+    pub fn set_deadline(&mut self, deadline: Instant) {
+        self.deadline = Some(deadline);
+    }
+
+    /// Set the callback invoked whenever `tick()` is about to continue polling.
+    pub(crate) fn set_on_poll(&mut self, callback: Option<PollCallback>) {
+        self.on_poll = callback;
+    }
+
+    /// Limit the specified sleep duration so that it does not extend past the deadline (if any).
+    fn capped(&self, duration: Duration) -> Duration {
+        match self.deadline {
+            Some(deadline) => duration.min(deadline.saturating_duration_since(Instant::now())),
+            None => duration,
+        }
+    }
+
+    /// Reset the ticker to its initial state, as if it had just been created from the same
+    /// ElementPoller. The elapsed time and number of attempts start again from zero, and any
+    /// check duration recorded for an `Adaptive` poller is discarded.
+    pub fn reset(&mut self) {
+        self.start = Instant::now();
+        self.cur_tries = 0;
+        self.last_check = None;
+    }
+
+    /// Get the number of polling attempts completed so far.
+    pub fn attempts(&self) -> u32 {
+        self.cur_tries
+    }
+
+    /// Get the time elapsed since polling started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
     pub async fn tick(&mut self) -> bool {
         self.cur_tries += 1;
 
-        if self.timeout.filter(|t| &self.start.elapsed() < t).is_none()
+        if let Some(deadline) = self.deadline {
+            if Instant::now() >= deadline {
+                return false;
+            }
+        } else if self.timeout.filter(|t| &self.start.elapsed() < t).is_none()
             && self.cur_tries >= self.min_tries
         {
             return false;
         }
 
-        if let Some(i) = self.interval {
-            // Next poll is due no earlier than this long after the first poll started.
-            let minimum_elapsed = i * self.cur_tries;
+        if let Some(callback) = &self.on_poll {
+            (callback.0)(self.cur_tries as usize, self.start.elapsed());
+        }
 
+        if self.spin {
+            yield_now().await;
+        }
+
+        if let Some(interval) = self.adaptive_interval() {
+            let duration = self.jittered(interval);
+            sleep(self.capped(duration)).await;
+        }
+
+        // Next poll is due no earlier than this long after the first poll started.
+        if let Some(minimum_elapsed) = self.minimum_elapsed() {
             // But this much time has elapsed since the first poll started.
             let actual_elapsed = self.start.elapsed();
 
             if actual_elapsed < minimum_elapsed {
                 // So we need to wait this much longer.
-                sleep(minimum_elapsed - actual_elapsed).await;
+                let duration = self.jittered(minimum_elapsed - actual_elapsed);
+                sleep(self.capped(duration)).await;
             }
         }
 
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_round_trip() {
+        let millis = Duration::from_millis;
+        let pollers = vec![
+            ElementPoller::NoWait,
+            ElementPoller::TimeoutWithInterval(Duration::new(10, 0), millis(500)),
+            ElementPoller::NumTriesWithInterval(3, millis(100)),
+            ElementPoller::TimeoutWithIntervalAndMinTries(Duration::new(10, 0), millis(100), 3),
+            ElementPoller::TimeoutWithIntervals(Duration::new(10, 0), vec![millis(1), millis(2)]),
+            ElementPoller::SpinMaxAttempts(3),
+            ElementPoller::Adaptive(Duration::new(10, 0), millis(100), millis(1000)),
+        ];
+        for poller in pollers {
+            let json = serde_json::to_string(&poller).unwrap();
+            let parsed: ElementPoller = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed, poller, "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_serde_shape() {
+        let secs = |secs: u64| serde_json::json!({ "secs": secs, "nanos": 0 });
+        let millis = |millis: u64| serde_json::json!({ "secs": 0, "nanos": millis * 1_000_000 });
+        let d = Duration::from_millis;
+        let cases = vec![
+            (ElementPoller::NoWait, serde_json::json!("NoWait")),
+            (
+                ElementPoller::TimeoutWithInterval(d(10_000), d(500)),
+                serde_json::json!({ "TimeoutWithInterval": [secs(10), millis(500)] }),
+            ),
+            (
+                ElementPoller::NumTriesWithInterval(5, d(1000)),
+                serde_json::json!({ "NumTriesWithInterval": [5, secs(1)] }),
+            ),
+            (
+                ElementPoller::TimeoutWithIntervalAndMinTries(d(10_000), d(500), 3),
+                serde_json::json!({ "TimeoutWithIntervalAndMinTries": [secs(10), millis(500), 3] }),
+            ),
+            (
+                ElementPoller::TimeoutWithIntervals(d(30_000), vec![d(1000)]),
+                serde_json::json!({ "TimeoutWithIntervals": [secs(30), [secs(1)]] }),
+            ),
+            (ElementPoller::SpinMaxAttempts(100), serde_json::json!({ "SpinMaxAttempts": 100 })),
+            (
+                ElementPoller::Adaptive(d(10_000), d(50), d(1000)),
+                serde_json::json!({ "Adaptive": [secs(10), millis(50), secs(1)] }),
+            ),
+        ];
+        for (poller, expected) in cases {
+            assert_eq!(serde_json::to_value(&poller).unwrap(), expected);
+            let parsed: ElementPoller = serde_json::from_value(expected).unwrap();
+            assert_eq!(parsed, poller);
+        }
+    }
+
+    #[test]
+    fn test_timeout_with_interval_validation() {
+        let secs = Duration::from_secs;
+        assert!(ElementPoller::timeout_with_interval(secs(5), secs(1)).is_ok());
+        assert!(ElementPoller::timeout_with_interval(secs(5), secs(5)).is_ok());
+        assert!(ElementPoller::timeout_with_interval(secs(1), secs(5)).is_err());
+        assert!(ElementPoller::timeout_with_interval(secs(0), secs(0)).is_err());
+        assert!(ElementPoller::timeout_with_interval(secs(5), secs(0)).is_err());
+    }
+
+    #[test]
+    fn test_empty_schedule_is_rejected() {
+        let poller = ElementPoller::TimeoutWithIntervals(Duration::new(10, 0), Vec::new());
+        assert!(ElementPollerTicker::try_new(poller.clone()).is_err());
+        // new() does not validate the poller, and polls without waiting instead.
+        let mut ticker = ElementPollerTicker::new(poller);
+        assert_eq!(ticker.minimum_elapsed(), None);
+        assert!(futures::executor::block_on(ticker.tick()));
+    }
+
+    #[test]
+    fn test_schedule_repeats_last_interval() {
+        let poller = ElementPoller::TimeoutWithIntervals(
+            Duration::new(10, 0),
+            vec![Duration::from_millis(100), Duration::from_millis(500)],
+        );
+        let mut ticker = ElementPollerTicker::new(poller);
+        let expected = [0, 100, 600, 1100, 1600];
+        for (tries, millis) in expected.iter().enumerate() {
+            ticker.cur_tries = tries as u32;
+            assert_eq!(ticker.minimum_elapsed(), Some(Duration::from_millis(*millis)));
+        }
+    }
+
+    #[test]
+    fn test_spin_max_attempts() {
+        let mut ticker = ElementPollerTicker::new(ElementPoller::SpinMaxAttempts(3));
+        let mut attempts = 1;
+        while futures::executor::block_on(ticker.tick()) {
+            attempts += 1;
+        }
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_reset() {
+        let millis = Duration::from_millis;
+        let pollers = vec![
+            ElementPoller::NoWait,
+            ElementPoller::TimeoutWithInterval(Duration::new(10, 0), millis(100)),
+            ElementPoller::NumTriesWithInterval(3, millis(100)),
+            ElementPoller::TimeoutWithIntervalAndMinTries(Duration::new(10, 0), millis(100), 3),
+            ElementPoller::TimeoutWithIntervals(Duration::new(10, 0), vec![millis(100)]),
+            ElementPoller::SpinMaxAttempts(3),
+            ElementPoller::Adaptive(Duration::new(10, 0), millis(100), millis(1000)),
+        ];
+        for poller in pollers {
+            let mut ticker = ElementPollerTicker::new(poller.clone());
+            let fresh = ElementPollerTicker::new(poller);
+            ticker.start -= Duration::new(5, 0);
+            ticker.cur_tries = 5;
+            ticker.record_check_duration(millis(300));
+
+            ticker.reset();
+            assert_eq!(ticker.attempts(), 0);
+            assert!(ticker.elapsed() < Duration::new(5, 0));
+            assert_eq!(ticker.minimum_elapsed(), fresh.minimum_elapsed());
+            assert_eq!(ticker.adaptive_interval(), fresh.adaptive_interval());
+            assert_eq!(ticker.timeout, fresh.timeout);
+            assert_eq!(ticker.min_tries, fresh.min_tries);
+        }
+    }
+
+    #[test]
+    fn test_spin_max_attempts_after_reset() {
+        let mut ticker = ElementPollerTicker::new(ElementPoller::SpinMaxAttempts(3));
+        while futures::executor::block_on(ticker.tick()) {}
+        ticker.reset();
+        let mut attempts = 1;
+        while futures::executor::block_on(ticker.tick()) {
+            attempts += 1;
+        }
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let poller = ElementPoller::TimeoutWithInterval(Duration::new(10, 0), Duration::new(1, 0));
+        let interval = Duration::from_millis(500);
+
+        let mut ticker = ElementPollerTicker::new(poller.clone());
+        ticker.set_jitter(0.0, Some(1));
+        assert_eq!(ticker.jittered(interval), interval);
+
+        let mut a = ElementPollerTicker::new(poller.clone());
+        let mut b = ElementPollerTicker::new(poller);
+        a.set_jitter(0.2, Some(42));
+        b.set_jitter(0.2, Some(42));
+        for _ in 0..100 {
+            let x = a.jittered(interval);
+            assert_eq!(x, b.jittered(interval));
+            assert!(x >= Duration::from_millis(400) && x <= Duration::from_millis(600));
+        }
+    }
+
+    #[test]
+    fn test_presets_are_valid() {
+        for preset in &[ElementPoller::QUICK, ElementPoller::STANDARD, ElementPoller::PATIENT] {
+            if let ElementPoller::TimeoutWithInterval(timeout, interval) = preset {
+                assert_eq!(
+                    ElementPoller::timeout_with_interval(*timeout, *interval).unwrap(),
+                    *preset
+                );
+            } else {
+                panic!("unexpected preset: {:?}", preset);
+            }
+        }
+    }
+
+    #[test]
+    fn test_deadline() {
+        let poller = ElementPoller::NumTriesWithInterval(3, Duration::from_millis(100));
+        let mut ticker = ElementPollerTicker::new(poller.clone());
+        ticker.set_deadline(Instant::now());
+        assert!(!futures::executor::block_on(ticker.tick()));
+
+        let mut ticker = ElementPollerTicker::new(poller);
+        ticker.set_deadline(Instant::now() + Duration::from_millis(50));
+        assert!(ticker.capped(Duration::new(10, 0)) <= Duration::from_millis(50));
+        assert_eq!(ticker.capped(Duration::from_millis(1)), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_on_poll() {
+        use std::sync::Mutex;
+
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let calls_clone = calls.clone();
+        let mut ticker = ElementPollerTicker::new(ElementPoller::SpinMaxAttempts(3));
+        ticker.set_on_poll(Some(PollCallback::new(move |attempt, _| {
+            calls_clone.lock().unwrap().push(attempt);
+        })));
+        while futures::executor::block_on(ticker.tick()) {}
+        assert_eq!(*calls.lock().unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_adaptive_interval_is_clamped() {
+        let poller = ElementPoller::Adaptive(
+            Duration::new(10, 0),
+            Duration::from_millis(100),
+            Duration::from_millis(1000),
+        );
+        let mut ticker = ElementPollerTicker::new(poller);
+        assert_eq!(ticker.adaptive_interval(), Some(Duration::from_millis(100)));
+        ticker.record_check_duration(Duration::from_millis(20));
+        assert_eq!(ticker.adaptive_interval(), Some(Duration::from_millis(100)));
+        ticker.record_check_duration(Duration::from_millis(300));
+        assert_eq!(ticker.adaptive_interval(), Some(Duration::from_millis(600)));
+        ticker.record_check_duration(Duration::from_millis(800));
+        assert_eq!(ticker.adaptive_interval(), Some(Duration::from_millis(1000)));
+    }
+}
@@ -0,0 +1,15 @@
+//! The prelude re-exports the most commonly used traits and types, including the traits
+//! required to use the query and waiter interfaces.
+//!
+//! ```ignore
+//! use thirtyfour_query::prelude::*;
+//! ```
+
+pub use crate::conditions::{
+    self, element_has_class, element_has_text, element_is_clickable, element_is_displayed,
+    element_is_enabled,
+};
+pub use crate::{
+    DefaultPollerConfig, DriverWaitable, ElementPoller, ElementQueryable, ElementWaitable,
+    StringMatch,
+};
@@ -1,46 +1,210 @@
+use std::fmt;
 use std::mem;
-use std::sync::Arc;
-use std::time::Duration;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use futures::Future;
 use stringmatch::Needle;
+use thirtyfour::components::select::SelectElement;
 use thirtyfour::error::{WebDriverError, WebDriverErrorInfo};
 use thirtyfour::prelude::{WebDriver, WebDriverResult};
-use thirtyfour::{By, WebDriverCommands, WebDriverSession, WebElement};
+use thirtyfour::{By, ScriptArgs, WebDriverCommands, WebDriverSession, WebElement};
 
-use crate::conditions::{handle_errors, negate};
-use crate::{conditions, ElementPoller, ElementPollerTicker, ElementPredicate};
+use crate::conditions::{handle_errors, negate, StabilityTracker};
+use crate::observer::notify_observer;
+use crate::poller::{default_poller, profile_poller, session_ticker, PollCallback};
+use crate::waiter::timestamped_path;
+use crate::{
+    conditions, ElementPoller, ElementPollerTicker, ElementPredicate, ElementWaiter, WaitError,
+};
 
 /// Get String containing comma-separated list of selectors used.
 fn get_selector_summary(selectors: &[ElementSelector]) -> String {
-    let criteria: Vec<String> = selectors.iter().map(|s| s.by.to_string()).collect();
+    let criteria: Vec<String> = selectors.iter().map(|s| s.as_by().to_string()).collect();
     format!("[{}]", criteria.join(","))
 }
 
-/// Helper function to return the NoSuchElement error struct.
-fn no_such_element(selectors: &[ElementSelector], description: &str) -> WebDriverError {
+/// Helper function to return the message for the NoSuchElement error.
+fn no_such_element_message(selectors: &[ElementSelector], description: &str) -> String {
     let element_description = if description.is_empty() {
         String::from("Element(s)")
     } else {
         format!("'{}' element(s)", description)
     };
 
-    WebDriverError::NoSuchElement(WebDriverErrorInfo::new(&format!(
+    format!(
         "{} not found using selectors: {}",
         element_description,
         &get_selector_summary(selectors)
-    )))
+    )
+}
+
+/// The maximum number of characters of the page source included by `diagnostics()`.
+const DIAGNOSTIC_SOURCE_LEN: usize = 2000;
+
+/// Get a description of the page source, truncated to at most `max_chars` characters.
+fn truncate_source(source: &str, max_chars: usize) -> String {
+    let total = source.chars().count();
+    if total <= max_chars {
+        format!("Page source:\n{}", source)
+    } else {
+        let truncated: String = source.chars().take(max_chars).collect();
+        format!("Page source (first {} of {} characters):\n{}", max_chars, total, truncated)
+    }
+}
+
+/// Whether queries swallow errors while finding elements, unless changed via
+/// `ElementQuery::retry_on_error()`.
+const DEFAULT_RETRY_ON_ERROR: bool = false;
+
+/// Handle the result of finding or filtering elements during a poll iteration. A
+/// `NoSuchElement` error means that nothing matched, and any other error is returned unless
+/// `retry_on_error` is true, in which case None is returned since it is unknown whether
+/// anything matched.
+fn handle_find_error<T>(
+    result: WebDriverResult<Vec<T>>,
+    retry_on_error: bool,
+) -> WebDriverResult<Option<Vec<T>>> {
+    match result {
+        Ok(x) => Ok(Some(x)),
+        Err(WebDriverError::NoSuchElement(_)) => Ok(Some(Vec::new())),
+        Err(_) if retry_on_error => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Run `find` from a root element, either the cached root (if `reuse` is true and a root has
+/// been cached) or a newly resolved one. If the cached root has gone stale, it is resolved
+/// again and `find` is retried once from the new root. See `ElementQuery::assume_stable_root()`.
+async fn find_under_root<R, T, RF, RFut, FF, FFut>(
+    cache: &Mutex<Option<R>>,
+    reuse: bool,
+    resolve: RF,
+    find: FF,
+) -> WebDriverResult<T>
+where
+    R: Clone,
+    RF: Fn() -> RFut,
+    RFut: Future<Output = WebDriverResult<R>>,
+    FF: Fn(R) -> FFut,
+    FFut: Future<Output = WebDriverResult<T>>,
+{
+    if reuse {
+        let cached = cache.lock().unwrap().clone();
+        if let Some(root) = cached {
+            match find(root).await {
+                Err(WebDriverError::StaleElementReference(_)) => {}
+                result => return result,
+            }
+        }
+    }
+
+    let root = resolve().await?;
+    if reuse {
+        *cache.lock().unwrap() = Some(root.clone());
+    }
+    find(root).await
+}
+
+/// Get the CSS selector for elements with the specified test id.
+///
+/// The attribute name is read from the "TestIdAttribute" session config key, and
+/// defaults to `data-testid`.
+fn test_id_css(session: &WebDriverSession, test_id: &str) -> String {
+    let attribute_name: String =
+        session.config().get("TestIdAttribute").unwrap_or_else(|_| String::from("data-testid"));
+    format!("[{}='{}']", attribute_name, test_id.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Script that removes duplicate elements and sorts the remaining elements in document order.
+const SORT_BY_DOCUMENT_ORDER_SCRIPT: &str = r#"
+    const elems = Array.from(new Set(arguments[0]));
+    elems.sort((a, b) => {
+        if (a === b) {
+            return 0;
+        }
+        return a.compareDocumentPosition(b) & Node.DOCUMENT_POSITION_FOLLOWING ? -1 : 1;
+    });
+    return elems;
+"#;
+
+/// The selector method used by an ElementSelector.
+/// This is either a thirtyfour `By` or a selector that needs to own its value.
+#[derive(Debug, Clone)]
+pub enum SelectorBy<'a> {
+    By(By<'a>),
+    Css(String),
+    XPath(String),
+}
+
+impl<'a> SelectorBy<'a> {
+    /// Get the thirtyfour `By` for this selector.
+    pub fn as_by(&self) -> By<'_> {
+        match self {
+            SelectorBy::By(by) => by.clone(),
+            SelectorBy::Css(css) => By::Css(css),
+            SelectorBy::XPath(xpath) => By::XPath(xpath),
+        }
+    }
+
+    /// Get the relative version of this selector, if it is a non-relative XPath.
+    /// See `ElementQuery::relative_xpath()` for the rewriting rules.
+    pub(crate) fn to_relative_xpath(&self) -> Option<SelectorBy<'static>> {
+        let xpath = match self {
+            SelectorBy::By(By::XPath(xpath)) => *xpath,
+            SelectorBy::XPath(xpath) => xpath.as_str(),
+            _ => return None,
+        };
+
+        if xpath.starts_with("//") {
+            Some(SelectorBy::XPath(format!(".{}", xpath)))
+        } else if xpath.starts_with("(//") {
+            Some(SelectorBy::XPath(format!("(.{}", &xpath[1..])))
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a> From<By<'a>> for SelectorBy<'a> {
+    fn from(by: By<'a>) -> Self {
+        SelectorBy::By(by)
+    }
+}
+
+impl fmt::Display for SelectorBy<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SelectorBy::By(by) => write!(f, "{}", by),
+            SelectorBy::Css(css) => write!(f, "Css({})", css),
+            SelectorBy::XPath(xpath) => write!(f, "XPath({})", xpath),
+        }
+    }
 }
 
 /// An ElementSelector contains a selector method (By) as well as zero or more filters.
 /// The filters will be applied to any elements matched by the selector.
 /// Selectors and filters all run in full on every poll iteration.
+///
+/// Create an ElementSelector with `new()`, and add filters with `add_filter()`. The filters
+/// are shared with any clones of the selector, e.g. via `ElementQuery::with_root()`, so they
+/// can only be read via `filters()`.
+#[derive(Clone)]
 pub struct ElementSelector<'a> {
     /// If false (default), find_elements() will be used. If true, find_element() will be used
     /// instead. See notes below for `with_single_selector()` for potential pitfalls.
     pub single: bool,
+    /// If true, this selector is checked before all selectors that are not preferred.
+    /// See `set_preferred()`.
+    preferred: bool,
+    /// The selector method. This is an empty CSS selector for selectors that need to own
+    /// their value, e.g. for test ids, so use `as_by()` to get the selector actually used.
     pub by: By<'a>,
-    pub filters: Vec<ElementPredicate>,
+    /// The selector method used instead of `by`, if it needs to own its value.
+    owned_by: Option<SelectorBy<'static>>,
+    filters: Vec<Arc<ElementPredicate>>,
 }
 
 impl<'a> ElementSelector<'a> {
@@ -51,11 +215,22 @@ impl<'a> ElementSelector<'a> {
     pub fn new(by: By<'a>) -> Self {
         Self {
             single: false,
-            by: by.clone(),
+            preferred: false,
+            by,
+            owned_by: None,
             filters: Vec::new(),
         }
     }
 
+    /// Create a selector for a selector method that owns its value, e.g. the CSS selector for
+    /// a test id. See `by`.
+    pub(crate) fn owned(by: SelectorBy<'static>) -> Self {
+        Self {
+            owned_by: Some(by),
+            ..Self::new(By::Css(""))
+        }
+    }
+
     //
     // Configurator
     //
@@ -68,9 +243,46 @@ impl<'a> ElementSelector<'a> {
         self.single = true;
     }
 
+    /// Call `set_preferred()` to have this selector checked before all selectors that are
+    /// not preferred within each poll iteration. See `ElementQuery::prefer()`.
+    pub fn set_preferred(&mut self) {
+        self.preferred = true;
+    }
+
     /// Add the specified filter to the list of filters for this selector.
     pub fn add_filter(&mut self, f: ElementPredicate) {
-        self.filters.push(f);
+        self.filters.push(Arc::new(f));
+    }
+
+    //
+    // Accessors
+    //
+
+    /// Get the thirtyfour `By` used by this selector.
+    pub fn as_by(&self) -> By<'_> {
+        match &self.owned_by {
+            Some(by) => by.as_by(),
+            None => self.by.clone(),
+        }
+    }
+
+    /// Get the selector method used by this selector, including any value it owns.
+    pub(crate) fn selector_by(&self) -> SelectorBy<'a> {
+        match &self.owned_by {
+            Some(by) => by.clone(),
+            None => SelectorBy::By(self.by.clone()),
+        }
+    }
+
+    /// Get the filters for this selector, in the order they were added.
+    pub fn filters(&self) -> impl Iterator<Item = &ElementPredicate> + '_ {
+        self.filters.iter().map(|f| f.as_ref())
+    }
+
+    /// Return true if this selector is checked before all selectors that are not preferred.
+    /// See `set_preferred()`.
+    pub fn is_preferred(&self) -> bool {
+        self.preferred
     }
 
     //
@@ -108,6 +320,22 @@ pub enum ElementQuerySource<'a> {
     Element(&'a WebElement<'a>),
 }
 
+impl<'a> ElementQuerySource<'a> {
+    /// Get the session that this source belongs to.
+    fn session(&self) -> &'a WebDriverSession {
+        match self {
+            ElementQuerySource::Driver(driver) => *driver,
+            ElementQuerySource::Element(element) => element.session,
+        }
+    }
+}
+
+/// Function signature for the fallback action of an ElementQuery.
+/// See `ElementQuery::or_else_action()`.
+type FallbackAction<'a> = Arc<
+    dyn Fn() -> Pin<Box<dyn Future<Output = WebDriverResult<()>> + Send + 'a>> + Send + Sync + 'a,
+>;
+
 /// High-level interface for performing powerful element queries using a
 /// builder pattern.
 ///
@@ -115,7 +343,7 @@ pub enum ElementQuerySource<'a> {
 /// ```rust
 /// # use thirtyfour::prelude::*;
 /// # use thirtyfour::support::block_on;
-/// # use thirtyfour_query::{ElementPoller, ElementQueryable};
+/// # use thirtyfour_query::{DefaultPollerConfig, ElementPoller, ElementQueryable};
 /// # use std::time::Duration;
 /// #
 /// # fn main() -> WebDriverResult<()> {
@@ -126,7 +354,7 @@ pub enum ElementQuerySource<'a> {
 /// driver.set_implicit_wait_timeout(Duration::new(0, 0)).await?;
 ///
 /// let poller = ElementPoller::TimeoutWithInterval(Duration::new(10, 0), Duration::from_millis(500));
-/// driver.config_mut().set("ElementPoller", poller)?;
+/// driver.set_default_poller(poller)?;
 ///
 /// driver.get("http://webappdemo").await?;
 ///
@@ -136,12 +364,25 @@ pub enum ElementQuerySource<'a> {
 /// #     })
 /// # }
 /// ```
+#[derive(Clone)]
 pub struct ElementQuery<'a> {
     source: Arc<ElementQuerySource<'a>>,
     poller: ElementPoller,
     selectors: Vec<ElementSelector<'a>>,
     ignore_errors: bool,
+    retry_on_error: bool,
     description: String,
+    timeout_message: Option<String>,
+    relative_xpath: bool,
+    root_by: Option<SelectorBy<'a>>,
+    assume_stable_root: bool,
+    root_cache: Arc<Mutex<Option<WebElement<'a>>>>,
+    log_counts: bool,
+    ordered: bool,
+    fallback_action: Option<FallbackAction<'a>>,
+    timeout_source_dir: Option<PathBuf>,
+    diagnostics: bool,
+    on_poll: Option<PollCallback>,
 }
 
 impl<'a> ElementQuery<'a> {
@@ -149,17 +390,59 @@ impl<'a> ElementQuery<'a> {
     // Constructor
     //
 
-    fn new(source: ElementQuerySource<'a>, poller: ElementPoller, by: By<'a>) -> Self {
-        let selector = ElementSelector::new(by.clone());
+    pub(crate) fn new(
+        source: ElementQuerySource<'a>,
+        poller: ElementPoller,
+        selectors: Vec<ElementSelector<'a>>,
+    ) -> Self {
         Self {
             source: Arc::new(source),
             poller,
-            selectors: vec![selector],
+            selectors,
             ignore_errors: true,
+            retry_on_error: DEFAULT_RETRY_ON_ERROR,
             description: String::new(),
+            timeout_message: None,
+            relative_xpath: false,
+            root_by: None,
+            assume_stable_root: false,
+            root_cache: Arc::new(Mutex::new(None)),
+            log_counts: false,
+            ordered: false,
+            fallback_action: None,
+            timeout_source_dir: None,
+            diagnostics: false,
+            on_poll: None,
         }
     }
 
+    /// Run the specified action if the first poll does not find any elements, and then
+    /// continue polling. This is useful when a setup action can create the element, e.g. to
+    /// open a panel if it isn't already open, and then find a field inside it:
+    ///
+    /// ```ignore
+    /// let field = driver
+    ///     .query(By::Id("search-field"))
+    ///     .or_else_action(|| async { toggle.click().await })
+    ///     .first()
+    ///     .await?;
+    /// ```
+    ///
+    /// The action runs at most once each time the query is executed, and the elements are
+    /// polled again immediately after it completes, even if the poller is `NoWait`. The poller
+    /// then starts again from the beginning, so its full timeout (or number of attempts)
+    /// applies to waiting for the elements created by the action. Any error
+    /// returned by the action is returned from the query. The action is not used by
+    /// `not_exists()`, since finding nothing is the desired outcome there.
+    pub fn or_else_action<F, Fut>(mut self, f: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'a,
+        Fut: Future<Output = WebDriverResult<()>> + Send + 'a,
+    {
+        self.fallback_action = Some(Arc::new(move || Box::pin(f())));
+        self
+    }
+
     /// Provide a name that will be included in the error message if the query was not successful.
     /// This is useful for providing more context about this particular query.
     pub fn desc(mut self, description: &str) -> Self {
@@ -167,14 +450,179 @@ impl<'a> ElementQuery<'a> {
         self
     }
 
-    /// By default a query will ignore any errors that occur while polling for the desired
-    /// element(s). However, this behaviour can be modified so that the waiter will return
-    /// early if an error is returned from thirtyfour.
+    /// Replace the error message returned if the query is not successful, e.g. to include
+    /// hints on how to fix the problem. This takes precedence over the message composed from
+    /// the description set via `desc()` and the selectors. The description is still used
+    /// elsewhere, e.g. by `explain()` and the `WaitObserver`.
+    pub fn timeout_message(mut self, message: &str) -> Self {
+        self.timeout_message = Some(message.to_string());
+        self
+    }
+
+    /// Call the specified function whenever the query is about to poll again, with the number
+    /// of attempts so far and the elapsed time, e.g. to print a heartbeat during long waits
+    /// in interactive runs. This is the query equivalent of `ElementWaiter::on_poll()`, see
+    /// there for details.
+    pub fn on_poll<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, Duration) + Send + Sync + 'static,
+    {
+        self.on_poll = Some(PollCallback::new(f));
+        self
+    }
+
+    /// Include the beginning of the page source in the NoSuchElement error returned when no
+    /// element was found, e.g. by `first()` or `all_required()`, in addition to the selectors
+    /// that were tried. This makes failures in CI much easier to diagnose from the error
+    /// alone. The source is truncated to the first 2000 characters.
+    ///
+    /// This costs one extra WebDriver request to fetch the page source, but only on the
+    /// failure path, so successful queries are not slowed down. If the source cannot be
+    /// fetched, the reason is included in the error instead. See `source_on_timeout()` for
+    /// saving the complete source to a file.
+    pub fn diagnostics(mut self, enabled: bool) -> Self {
+        self.diagnostics = enabled;
+        self
+    }
+
+    /// Save the page source (HTML) to the specified directory whenever this query times out,
+    /// to help debug why a locator did not match. This is much cheaper than a screenshot,
+    /// and often more useful. The file is named after the description set via `desc()` (if
+    /// any) and the current timestamp.
+    ///
+    /// The source is saved before the query returns, whether the timeout results in an error
+    /// (e.g. for `first()`) or not (e.g. for `all()`, which returns an empty Vec). Failing to
+    /// save the source does not affect the result of the query. With the `log` feature
+    /// enabled, the failure is logged as a warning.
+    pub fn source_on_timeout<P>(mut self, dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.timeout_source_dir = Some(dir.into());
+        self
+    }
+
+    /// By default the filters of a query will ignore any errors that occur while checking
+    /// an element, and simply treat the element as not matching. However, this behaviour can
+    /// be modified so that the query will return early if an error is returned from thirtyfour.
+    ///
+    /// NOTE: Filters capture this setting when they are added, so this should be called before
+    /// adding any filters to the query. Errors from finding the elements themselves are not
+    /// affected by this setting, see `retry_on_error()`.
     pub fn ignore_errors(mut self, ignore: bool) -> Self {
         self.ignore_errors = ignore;
         self
     }
 
+    /// By default, any error other than `NoSuchElement` that occurs while finding or filtering
+    /// elements during a poll iteration (e.g. an invalid selector, or a lost session) is
+    /// returned from the query immediately. Setting this to true swallows such errors instead,
+    /// and tries again on the next poll iteration, e.g. to ride out a transient error while the
+    /// page is navigating. A selector that failed is treated as neither matching nor empty in
+    /// that poll iteration, so an error never satisfies `not_exists()` or `none()` either.
+    ///
+    /// NOTE: With this enabled, a persistent error such as an invalid selector only shows up
+    /// as the query timing out, so it is disabled by default.
+    pub fn retry_on_error(mut self, retry: bool) -> Self {
+        self.retry_on_error = retry;
+        self
+    }
+
+    /// When querying from an element, XPath selectors starting with `//` still search the
+    /// whole document rather than the element's subtree. Setting this to true will rewrite
+    /// such selectors to be relative to the element instead, i.e. `//div` becomes `.//div`
+    /// and `(//div)[1]` becomes `(.//div)[1]`. All other selectors are left unchanged.
+    ///
+    /// This has no effect for queries from the driver (unless a root is set via
+    /// `with_root_selector()`), and is disabled by default.
+    pub fn relative_xpath(mut self, relative: bool) -> Self {
+        self.relative_xpath = relative;
+        self
+    }
+
+    /// Log the number of elements matched by each selector on every poll iteration, at the
+    /// `debug` level. This is useful during development to see how the number of matching
+    /// elements changes while polling.
+    ///
+    /// NOTE: This requires the `log` feature. Without it, this setting has no effect.
+    pub fn log_counts(mut self, enabled: bool) -> Self {
+        self.log_counts = enabled;
+        self
+    }
+
+    /// By default, the elements returned by a query are those matched by the first selector
+    /// (out of those added via `or()`) that matches any elements. They are returned in the order
+    /// returned by the WebDriver, which is typically (but not guaranteed to be) document order.
+    ///
+    /// Calling `ordered()` changes this so that the elements matched by all selectors are
+    /// combined, any duplicates (i.e. elements matched by more than one selector) are removed,
+    /// and the result is sorted in document order. This requires running every selector on
+    /// each poll iteration, plus an additional script execution to sort the elements.
+    pub fn ordered(mut self) -> Self {
+        self.ordered = true;
+        self
+    }
+
+    /// Return a copy of this ElementQuery (including all selectors and filters) that queries
+    /// from the specified root element instead. This is useful for building a query once and
+    /// then running it against multiple containers.
+    ///
+    /// Note that the semantics of some selectors depend on the root. In particular, XPath
+    /// selectors starting with `//` still search the whole document when run from an element.
+    /// See `relative_xpath()` for more details.
+    ///
+    /// The root element is used as-is on every poll iteration, without being found again or
+    /// checked first, so polling from a root costs no more requests than polling from the
+    /// driver. If the root goes stale (e.g. its container is re-rendered), every find returns
+    /// a stale element reference error, which is returned from the query (or, with
+    /// `retry_on_error()`, keeps the query polling without matching until it times out). In
+    /// that case, find the root again and run the query from the new root, or use
+    /// `with_root_selector()` to have the query find the root itself.
+    pub fn with_root(&self, root: &'a WebElement<'a>) -> Self {
+        let mut query = self.clone();
+        query.source = Arc::new(ElementQuerySource::Element(root));
+        query.root_cache = Arc::new(Mutex::new(None));
+        query
+    }
+
+    /// Return a copy of this ElementQuery (including all selectors and filters) that queries
+    /// from the first element matching the specified root selector. The root is found from the
+    /// source of this query, i.e. the driver or the element the query was created from.
+    ///
+    /// By default the root is found again on every poll iteration, so the query keeps working
+    /// if the root is re-rendered, at the cost of one extra request per selector and poll. If
+    /// no root is found, the poll iteration does not match anything. See
+    /// `assume_stable_root()` to find the root only once instead.
+    ///
+    /// ```ignore
+    /// let rows = driver
+    ///     .query(By::Css("tr"))
+    ///     .with_root_selector(By::Id("results"))
+    ///     .assume_stable_root(true)
+    ///     .all()
+    ///     .await?;
+    /// ```
+    pub fn with_root_selector(&self, root: By<'a>) -> Self {
+        let mut query = self.clone();
+        query.root_by = Some(SelectorBy::By(root));
+        query.root_cache = Arc::new(Mutex::new(None));
+        query
+    }
+
+    /// Find the root set via `with_root_selector()` only once, and reuse it for all later poll
+    /// iterations (and runs) of this query, instead of finding it again on every poll. This
+    /// saves one request per selector and poll.
+    ///
+    /// If a find from the reused root returns a stale element reference error, e.g. because
+    /// the container was re-rendered, the root is found again and the find is retried once
+    /// from the new root, so a stale root does not fail the query. The cached root is shared
+    /// with clones of this query. This has no effect without `with_root_selector()`, and is
+    /// disabled by default.
+    pub fn assume_stable_root(mut self, stable: bool) -> Self {
+        self.assume_stable_root = stable;
+        self
+    }
+
     //
     // Poller / Waiter
     //
@@ -186,6 +634,18 @@ impl<'a> ElementQuery<'a> {
         self
     }
 
+    /// Use the ElementPoller registered under the specified profile name for this ElementQuery.
+    /// See `DefaultPollerConfig::set_poller_profile()`.
+    ///
+    /// If no profile with that name has been registered, the poller is left unchanged, i.e.
+    /// the default poller is used unless another poller was already set for this ElementQuery.
+    pub fn use_profile(mut self, name: &str) -> Self {
+        if let Some(poller) = profile_poller(self.source.session(), name) {
+            self.poller = poller;
+        }
+        self
+    }
+
     /// Force this ElementQuery to wait for the specified timeout, polling once
     /// after each interval. This will override the poller for this
     /// ElementQuery only.
@@ -217,34 +677,426 @@ impl<'a> ElementQuery<'a> {
         self.add_selector(ElementSelector::new(by))
     }
 
+    /// Add a new preferred selector to this ElementQuery, e.g. a stable test id that should
+    /// win over a more brittle CSS path whenever both match:
+    ///
+    /// ```ignore
+    /// let elem = driver
+    ///     .query(By::Css("form > div:nth-child(3) button"))
+    ///     .prefer(By::Css("[data-testid='submit']"))
+    ///     .first()
+    ///     .await?;
+    /// ```
+    ///
+    /// Within each poll, preferred selectors are checked before all other selectors, and
+    /// otherwise selectors are checked in the order they were added, so the first matching
+    /// preferred selector wins even if an earlier selector also matches. If no preferred
+    /// selector matches, the other selectors are used as for `or()`. Like `or()`, all
+    /// conditions specified after this selector (up until the next `or()` or `prefer()`)
+    /// apply to it.
+    ///
+    /// This also applies to `race()` and `first_with_selector()`, but the index returned by
+    /// `race()` is still the position in which the selector was added. Preference has no
+    /// effect on `ordered()` queries, which combine the matches of all selectors.
+    pub fn prefer(self, by: By<'a>) -> Self {
+        let mut selector = ElementSelector::new(by);
+        selector.set_preferred();
+        self.add_selector(selector)
+    }
+
+    /// Add a new selector to this ElementQuery using the specified CSS selector.
+    /// This is shorthand for `or(By::Css(css))`.
+    pub fn or_css(self, css: &'a str) -> Self {
+        self.or(By::Css(css))
+    }
+
+    /// Add a new selector to this ElementQuery using the specified XPath.
+    /// This is shorthand for `or(By::XPath(xpath))`.
+    pub fn or_xpath(self, xpath: &'a str) -> Self {
+        self.or(By::XPath(xpath))
+    }
+
+    /// Add a new selector to this ElementQuery that matches elements with the specified id.
+    /// This is shorthand for `or(By::Id(id))`.
+    pub fn or_id(self, id: &'a str) -> Self {
+        self.or(By::Id(id))
+    }
+
+    /// Add a new selector to this ElementQuery that matches links with the specified text.
+    /// This is shorthand for `or(By::LinkText(text))`.
+    ///
+    /// The link text is matched by the driver, against the rendered text of the link as
+    /// returned by `WebElement::text()`. This means that leading and trailing whitespace is
+    /// trimmed and text hidden via CSS is excluded, and that `text-transform` may affect the
+    /// text in some drivers. The match is exact and case-sensitive.
+    pub fn or_link_text(self, text: &'a str) -> Self {
+        self.or(By::LinkText(text))
+    }
+
+    /// Add a new selector to this ElementQuery that matches links whose text contains the
+    /// specified text. This is shorthand for `or(By::PartialLinkText(text))`.
+    /// See `or_link_text()` for how the driver matches link text.
+    pub fn or_partial_link_text(self, text: &'a str) -> Self {
+        self.or(By::PartialLinkText(text))
+    }
+
+    /// Add a new selector to this ElementQuery that matches elements with the specified test id.
+    /// See `ElementQueryable::query_testid()` for more details.
+    pub fn or_testid(self, test_id: &str) -> Self {
+        let css = test_id_css(self.source.session(), test_id);
+        self.add_selector(ElementSelector::owned(SelectorBy::Css(css)))
+    }
+
+    /// Return a human-readable description of this query, including its selectors, filters,
+    /// root and poller. This is useful for logging and debugging. It does not perform any
+    /// WebDriver requests.
+    pub fn explain(&self) -> String {
+        let selectors: Vec<String> = self
+            .selectors
+            .iter()
+            .map(|s| {
+                let mut desc = s.as_by().to_string();
+                if !s.filters.is_empty() {
+                    desc.push_str(&format!(" with {} filter(s)", s.filters.len()));
+                }
+                if s.single {
+                    desc.push_str(" (single)");
+                }
+                desc
+            })
+            .collect();
+
+        let mut explanation = String::from("Query");
+        if !self.description.is_empty() {
+            explanation.push_str(&format!(" '{}'", self.description));
+        }
+        explanation.push_str(&format!(" [{}]", selectors.join(", ")));
+        explanation.push_str(match self.source.as_ref() {
+            ElementQuerySource::Driver(_) => " from driver",
+            ElementQuerySource::Element(_) => " from element",
+        });
+        if let Some(root_by) = &self.root_by {
+            explanation.push_str(&format!(" within {}", root_by));
+            if self.assume_stable_root {
+                explanation.push_str(" (stable)");
+            }
+        }
+        explanation.push_str(&format!(", poller {:?}", self.poller));
+        if !self.ignore_errors {
+            explanation.push_str(", fail on errors");
+        }
+        if self.retry_on_error {
+            explanation.push_str(", retry on errors");
+        }
+        if self.ordered {
+            explanation.push_str(", ordered");
+        }
+        if self.relative_xpath {
+            explanation.push_str(", relative xpath");
+        }
+        explanation
+    }
+
     //
     // Retrievers
     //
 
     /// Return true if an element matches any selector, otherwise false.
+    ///
+    /// This polls until an element matches or the poller times out. A timeout is not an error,
+    /// it simply returns false.
     pub async fn exists(&self) -> WebDriverResult<bool> {
         let elements = self.run_poller(false).await?;
         Ok(!elements.is_empty())
     }
 
+    /// Return true if an element matches any selector before the poller times out, otherwise
+    /// false. This is the same as `exists()`, but reads more clearly when the element not
+    /// appearing is an expected outcome to branch on, e.g. an optional cookie banner:
+    ///
+    /// ```ignore
+    /// if driver.query(By::Id("cookie-banner")).appears().await? {
+    ///     driver.find_element(By::Id("accept-cookies")).await?.click().await?;
+    /// }
+    /// ```
+    ///
+    /// Like `exists()`, this never returns a Timeout error. Only errors that abort polling
+    /// (see `retry_on_error()`) are returned.
+    pub async fn appears(&self) -> WebDriverResult<bool> {
+        self.exists().await
+    }
+
     /// Return true if no element matches any selector, otherwise false.
     pub async fn not_exists(&self) -> WebDriverResult<bool> {
         let elements = self.run_poller(true).await?;
         Ok(elements.is_empty())
     }
 
+    /// Wait until no element matches any of the selectors, e.g. until all loading indicators
+    /// have disappeared. This is the waiting counterpart to `not_exists()`, and unlike
+    /// `not_exists()` it requires every selector to stop matching, not just one of them.
+    ///
+    /// Returns a Timeout error listing the selectors that still matched if any elements
+    /// remain when the poller times out. With `retry_on_error()`, a selector that fails with
+    /// an error counts as still matching, since it is unknown whether its elements are gone.
+    pub async fn none(&self) -> WebDriverResult<()> {
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let mut still_matched = Vec::new();
+                for selector in &self.selectors {
+                    match self.fetch_matches(selector, ticker.attempts() + 1).await? {
+                        Some(elements) if elements.is_empty() => {}
+                        _ => still_matched.push(selector.as_by().to_string()),
+                    }
+                }
+
+                if still_matched.is_empty() {
+                    return Ok(());
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    let message =
+                        format!("Elements still matched selectors: [{}]", still_matched.join(","));
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
     /// Return only the first WebElement that matches any selector (including all of
     /// the filters for that selector).
     pub async fn first(&self) -> WebDriverResult<WebElement<'a>> {
         let mut elements = self.run_poller(false).await?;
 
         if elements.is_empty() {
-            Err(no_such_element(&self.selectors, &self.description))
+            Err(self.not_found_error_with_diagnostics().await)
         } else {
             Ok(elements.remove(0))
         }
     }
 
+    /// Return the element in the specified cache if it is still present, and otherwise find
+    /// the first matching WebElement as for `first()` and store it in the cache. This is an
+    /// explicit caching primitive for loops that use the same element repeatedly:
+    ///
+    /// ```ignore
+    /// let query = driver.query(By::Id("counter"));
+    /// let mut cache = None;
+    /// for _ in 0..10 {
+    ///     let counter = query.first_cached(&mut cache).await?;
+    ///     ...
+    /// }
+    /// ```
+    ///
+    /// Before reusing the cached element, this checks that it is still attached to the
+    /// document, which takes one WebDriver request (see `WebElement::is_present()`) but
+    /// avoids running the selectors and filters again. The cached element is not checked
+    /// against the filters again, so it is returned even if it no longer matches them, e.g.
+    /// if it has been hidden. If the element has to be found again and cannot be found, the
+    /// error is returned and the cache is left empty.
+    pub async fn first_cached(
+        &self,
+        cache: &mut Option<WebElement<'a>>,
+    ) -> WebDriverResult<WebElement<'a>> {
+        if let Some(elem) = cache.take() {
+            if elem.is_present().await.unwrap_or(false) {
+                *cache = Some(elem.clone());
+                return Ok(elem);
+            }
+        }
+        let elem = self.first().await?;
+        *cache = Some(elem.clone());
+        Ok(elem)
+    }
+
+    /// Find the first matching WebElement and convert it into a higher-level wrapper using the
+    /// specified async constructor.
+    ///
+    /// ```ignore
+    /// let select = driver
+    ///     .query(By::Id("country"))
+    ///     .first_as(|elem| async move { SelectElement::new(&elem).await })
+    ///     .await?;
+    /// ```
+    ///
+    /// The query's polling and stale element handling only apply to resolving the element.
+    /// The constructor is called once with the resolved element, and any error it returns is
+    /// returned as-is. See `first_select()` for `<select>` elements.
+    pub async fn first_as<T, F, Fut>(&self, constructor: F) -> WebDriverResult<T>
+    where
+        F: FnOnce(WebElement<'a>) -> Fut,
+        Fut: Future<Output = WebDriverResult<T>>,
+    {
+        let elem = self.first().await?;
+        constructor(elem).await
+    }
+
+    /// Find the first matching `<select>` element and wrap it in thirtyfour's `SelectElement`.
+    /// This is equivalent to `first_as()` with `SelectElement::new()`.
+    pub async fn first_select(&self) -> WebDriverResult<SelectElement<'a>> {
+        let elem = self.first().await?;
+        SelectElement::new(&elem).await
+    }
+
+    /// Find the first matching WebElement and then wait until it satisfies the specified
+    /// condition, under a single poller, and return the element. This is the combined form of
+    /// `query(..).first()` followed by `wait_until()`, which would otherwise apply the timeout
+    /// twice:
+    ///
+    /// ```ignore
+    /// let button = driver
+    ///     .query(By::Id("submit"))
+    ///     .first_then_wait(conditions::element_is_clickable(true))
+    ///     .await?;
+    /// ```
+    ///
+    /// The element is kept between poll iterations, and is only found again if it is no longer
+    /// present, e.g. after a re-render. This is checked whenever the condition is not met or
+    /// returns a stale element or no such element error, so the condition may use any error
+    /// handling. Any other error from the condition is returned, unless `retry_on_error()` is
+    /// enabled for this query.
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) if no element was found before the poller
+    /// timed out, or a Timeout error if an element was found but never satisfied the condition.
+    pub async fn first_then_wait(
+        &self,
+        condition: ElementPredicate,
+    ) -> WebDriverResult<WebElement<'a>> {
+        let mut ticker = self.new_ticker()?;
+        let mut element: Option<WebElement<'a>> = None;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                if element.is_none() {
+                    let elements = self.poll_once(ticker.attempts() + 1, false).await?;
+                    element = elements.and_then(|x| x.into_iter().next());
+                }
+
+                if let Some(elem) = &element {
+                    let present = match condition(elem).await {
+                        Ok(true) => return Ok(elem.clone()),
+                        Ok(false) => elem.is_present().await.unwrap_or(false),
+                        Err(WebDriverError::StaleElementReference(_))
+                        | Err(WebDriverError::NoSuchElement(_)) => false,
+                        Err(_) if self.retry_on_error => true,
+                        Err(e) => return Err(e),
+                    };
+                    if !present {
+                        element = None;
+                    }
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    if element.is_none() {
+                        self.save_timeout_source().await;
+                        return Err(self.not_found_error_with_diagnostics().await);
+                    }
+                    let message = format!(
+                        "Element did not satisfy the condition using selectors: {}",
+                        get_selector_summary(&self.selectors)
+                    );
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
+    /// Find the first matching WebElement, and return an ElementWaiter for it with the specified
+    /// error message (see `ElementWaiter::error()`), so that finding an element and waiting
+    /// for its state can be chained:
+    ///
+    /// ```ignore
+    /// driver.query(By::Id("submit")).first_wait_until("ready").await?.clickable().await?;
+    /// ```
+    ///
+    /// Unlike `first_then_wait()`, finding the element and waiting on it are two separate
+    /// steps with separate timeouts: the poller of this query is used to find the element, and
+    /// the default poller is used by the waiter unless overridden via `with_poller()`. The
+    /// element is not found again if it goes stale while waiting (see
+    /// `ElementWaiter::refetch_on_stale()`). Returns Err(WebDriverError::NoSuchElement) if no
+    /// element was found.
+    pub async fn first_wait_until(&self, message: &str) -> WebDriverResult<ElementWaiter<'a>> {
+        let element = self.first().await?;
+        let poller = default_poller(element.session);
+        Ok(ElementWaiter::owned(element, poller).error(message))
+    }
+
+    /// Return the first WebElement that matches any selector, together with the selector that
+    /// matched it. This is useful for branching on which alternative added via `or()` was
+    /// found, e.g. to handle A/B variants of a page differently.
+    ///
+    /// Selectors are checked in the order they were added (except that selectors added via
+    /// `prefer()` are checked first), and the first selector that matches any elements
+    /// (including all of its filters) wins, even if a later selector matches an element that
+    /// comes earlier in the document. This is the case even if
+    /// `ordered()` is enabled. The returned selector is the one originally specified, not the
+    /// rewritten version used for `relative_xpath()`. It borrows from this query, so that
+    /// selectors that own their value (e.g. from `or_testid()`) can be returned as well.
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
+    pub async fn first_with_selector(&self) -> WebDriverResult<(WebElement<'a>, By<'_>)> {
+        let (index, element) = self.race().await?;
+        Ok((element, self.selectors[index].as_by()))
+    }
+
+    /// Wait until any of the selectors added via `or()` matches, and return the index of the
+    /// selector that won the race (0 for the selector passed to `query()`, 1 for the first
+    /// `or()`, and so on) together with the first element it matched. This returns as soon as
+    /// any selector matches in a poll iteration, which is useful for branching on which of
+    /// several possible outcomes happened:
+    ///
+    /// ```ignore
+    /// let (index, _) = driver.query(By::Id("success")).or(By::Id("error")).race().await?;
+    /// if index == 1 {
+    ///     panic!("The form submission failed");
+    /// }
+    /// ```
+    ///
+    /// If more than one selector would match in the same poll iteration, the tie is broken by
+    /// the order in which the selectors were added (with selectors added via `prefer()`
+    /// first), since they are checked in that order and the first match ends the iteration.
+    /// See `first_with_selector()` to get the selector rather than its index.
+    ///
+    /// Returns Err(WebDriverError::NoSuchElement) if no elements match.
+    pub async fn race(&self) -> WebDriverResult<(usize, WebElement<'a>)> {
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                for (index, selector) in self.prioritized_selectors() {
+                    if let Some(mut elements) =
+                        self.fetch_matches(selector, ticker.attempts() + 1).await?
+                    {
+                        if !elements.is_empty() {
+                            return Ok((index, elements.remove(0)));
+                        }
+                    }
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    return Err(self.not_found_error_with_diagnostics().await);
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
     /// Return all WebElements that match any one selector (including all of the
     /// filters for that selector).
     ///
@@ -261,25 +1113,568 @@ impl<'a> ElementQuery<'a> {
         let elements = self.run_poller(false).await?;
 
         if elements.is_empty() {
-            Err(no_such_element(&self.selectors, &self.description))
+            Err(self.not_found_error_with_diagnostics().await)
         } else {
             Ok(elements)
         }
     }
 
+    /// Run this query under each of the specified root elements, and return all matching
+    /// elements combined, e.g. to collect the error messages from several form sections:
+    ///
+    /// ```ignore
+    /// let sections = driver.query(By::Css("form section")).all().await?;
+    /// let errors = driver.query(By::Css(".error")).under_all(&sections).await?;
+    /// ```
+    ///
+    /// On each poll iteration, the matches under every root are resolved as for `all()`, then
+    /// combined. Elements found under more than one root (e.g. if one root contains another)
+    /// are only returned once, and the result is sorted in document order, regardless of the
+    /// order of the roots. This polls until at least one element matches under any root, and
+    /// returns an empty Vec if the poller times out (or if there are no roots).
+    ///
+    /// As for `with_root()`, XPath selectors starting with `//` still search the whole document
+    /// unless `relative_xpath()` is enabled.
+    pub async fn under_all(
+        &self,
+        roots: &'a [WebElement<'a>],
+    ) -> WebDriverResult<Vec<WebElement<'a>>> {
+        if roots.is_empty() {
+            return Ok(Vec::new());
+        }
+        let queries: Vec<ElementQuery<'a>> =
+            roots.iter().map(|root| self.with_root(root)).collect();
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let mut combined = Vec::new();
+                for query in &queries {
+                    let elements = query.poll_once(ticker.attempts() + 1, false).await?;
+                    combined.extend(elements.unwrap_or_default());
+                }
+                if !combined.is_empty() {
+                    return self.sort_by_document_order(combined).await;
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    return Ok(Vec::new());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, matches!(&result, Ok(x) if !x.is_empty()));
+        result
+    }
+
+    /// Wait until the number of matching elements has been the same for the specified number
+    /// of consecutive poll iterations, and return the matching elements. This is useful for
+    /// lists that load incrementally (e.g. infinite scroll), where the final count is unknown.
+    ///
+    /// Note that a pause in loading that lasts longer than `polls` iterations will be mistaken
+    /// for the list being complete, so choose `polls` (together with the poll interval) to
+    /// cover the longest expected pause. A count of zero can also be stable, in which case an
+    /// empty Vec is returned.
+    ///
+    /// Returns a Timeout error if the count does not stabilize before the poller times out.
+    pub async fn count_stable(&self, polls: usize) -> WebDriverResult<Vec<WebElement<'a>>> {
+        let mut ticker = self.new_ticker()?;
+        let mut tracker = StabilityTracker::new();
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let elements =
+                    self.poll_once(ticker.attempts() + 1, false).await?.unwrap_or_default();
+                if tracker.observe(elements.len()) >= polls {
+                    return Ok(elements);
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    let message = format!(
+                        "Element count did not stabilize using selectors: {}",
+                        get_selector_summary(&self.selectors)
+                    );
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
+    /// Wait until the number of matching elements satisfies the specified predicate, and
+    /// return the matching elements, e.g. for a count within a range:
+    ///
+    /// ```ignore
+    /// let items = driver.query(By::Css(".item")).count_matches(|n| (3..=7).contains(&n)).await?;
+    /// ```
+    ///
+    /// The predicate is evaluated on the number of matching elements (after filters) on each
+    /// poll iteration, not on the elements themselves. Use filters to restrict which elements
+    /// are counted. A count of zero is passed to the predicate as well, so this can also wait
+    /// for all matches to disappear.
+    ///
+    /// Returns a Timeout error including the last observed count if the predicate is not
+    /// satisfied before the poller times out.
+    pub async fn count_matches<F>(&self, f: F) -> WebDriverResult<Vec<WebElement<'a>>>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let elements = self.poll_once(ticker.attempts() + 1, false).await?.unwrap_or_default();
+                if f(elements.len()) {
+                    return Ok(elements);
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    let message = format!(
+                        "Element count did not satisfy the predicate (found {}) using selectors: {}",
+                        elements.len(),
+                        get_selector_summary(&self.selectors)
+                    );
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
+    /// Take a snapshot of the current number of matching elements, then wait until it has
+    /// decreased by exactly `delta`, and return the matching elements. This is useful for
+    /// waiting until rows have been removed from a list.
+    ///
+    /// The snapshot is taken by the first poll, so this must be called before the removal has
+    /// completed. If the removal may already have happened by then, the snapshot will include
+    /// it and this will wait for a further decrease that never happens. In that case, capture
+    /// the count before triggering the removal and use `count_decreased_from()` instead:
+    ///
+    /// ```ignore
+    /// let baseline = driver.query(By::Css("tr")).nowait().all().await?.len();
+    /// delete_button.click().await?;
+    /// driver.query(By::Css("tr")).count_decreased_from(baseline, 1).await?;
+    /// ```
+    pub async fn count_decreased_by(&self, delta: usize) -> WebDriverResult<Vec<WebElement<'a>>> {
+        let baseline = self.poll_once(1, false).await?.unwrap_or_default().len();
+        self.count_decreased_from(baseline, delta).await
+    }
+
+    /// Wait until the number of matching elements is exactly `delta` less than the specified
+    /// baseline count, and return the matching elements. See `count_decreased_by()`.
+    ///
+    /// Returns an error immediately if `delta` is greater than `baseline`, or a Timeout error
+    /// if the expected count is not reached before the poller times out.
+    pub async fn count_decreased_from(
+        &self,
+        baseline: usize,
+        delta: usize,
+    ) -> WebDriverResult<Vec<WebElement<'a>>> {
+        let expected = baseline.checked_sub(delta).ok_or_else(|| {
+            WebDriverError::CustomError(format!(
+                "Cannot wait for the element count to decrease by {} from {}",
+                delta, baseline
+            ))
+        })?;
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let elements = self.poll_once(ticker.attempts() + 1, false).await?.unwrap_or_default();
+                if elements.len() == expected {
+                    return Ok(elements);
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    let message = format!(
+                        "Element count did not decrease from {} to {} (found {}) using selectors: {}",
+                        baseline,
+                        expected,
+                        elements.len(),
+                        get_selector_summary(&self.selectors)
+                    );
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
+    /// Wait until exactly the specified number of elements match, and return an assertion-style
+    /// error if they don't, e.g. `expected 3 elements matching 'rows', found 2`. This reads
+    /// naturally in test bodies:
+    ///
+    /// ```ignore
+    /// driver.query(By::Css(".row")).desc("rows").assert_count(3).await?;
+    /// ```
+    ///
+    /// This still polls until the count matches or the poller times out, so the error is only
+    /// returned once the count has failed to match for the whole timeout. The description set
+    /// via `desc()` is used in the message if provided, otherwise the selectors are listed.
+    pub async fn assert_count(&self, expected: usize) -> WebDriverResult<()> {
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let found =
+                    self.poll_once(ticker.attempts() + 1, false).await?.unwrap_or_default().len();
+                if found == expected {
+                    return Ok(());
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    let description = if self.description.is_empty() {
+                        get_selector_summary(&self.selectors)
+                    } else {
+                        self.description.clone()
+                    };
+                    let message = format!(
+                        "expected {} elements matching '{}', found {}",
+                        expected, description, found
+                    );
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
+    /// Wait until exactly `n` of the matching elements are checked, e.g. in a checkbox group,
+    /// and return the checked elements.
+    ///
+    /// This reads the live `checked` DOM property of each match (see
+    /// `conditions::element_property_checked()`), not the `checked` HTML attribute, which only
+    /// reflects the initial state. Returns a Timeout error if the count does not match before
+    /// the poller times out.
+    pub async fn selected_count(&self, n: usize) -> WebDriverResult<Vec<WebElement<'a>>> {
+        self.checked_count_until(|count| count == n, &format!("exactly {}", n)).await
+    }
+
+    /// Wait until at least `n` of the matching elements are checked, and return the checked
+    /// elements. See `selected_count()`.
+    pub async fn count_checked_at_least(&self, n: usize) -> WebDriverResult<Vec<WebElement<'a>>> {
+        self.checked_count_until(|count| count >= n, &format!("at least {}", n)).await
+    }
+
+    /// Poll until the number of matching elements that are checked is accepted by the
+    /// specified function, and return the checked elements.
+    async fn checked_count_until<F>(
+        &self,
+        accept: F,
+        expected: &str,
+    ) -> WebDriverResult<Vec<WebElement<'a>>>
+    where
+        F: Fn(usize) -> bool,
+    {
+        let is_checked = conditions::element_property_checked(self.ignore_errors);
+        let mut ticker = self.new_ticker()?;
+
+        let result = async {
+            loop {
+                let check_start = Instant::now();
+                let elements =
+                    self.poll_once(ticker.attempts() + 1, false).await?.unwrap_or_default();
+                let mut checked = Vec::new();
+                for elem in elements {
+                    if is_checked(&elem).await? {
+                        checked.push(elem);
+                    }
+                }
+                if accept(checked.len()) {
+                    return Ok(checked);
+                }
+
+                ticker.record_check_duration(check_start.elapsed());
+                if !ticker.tick().await {
+                    let message = format!(
+                        "Expected {} checked elements (found {}) using selectors: {}",
+                        expected,
+                        checked.len(),
+                        get_selector_summary(&self.selectors)
+                    );
+                    return Err(self.wait_error(&message, &ticker).await.into());
+                }
+            }
+        }
+        .await;
+        self.notify_wait(&ticker, result.is_ok());
+        result
+    }
+
+    /// Run the specified action against every matching element, and return the number of
+    /// elements processed.
+    ///
+    /// The matches are resolved as for `all()`. If the action returns a stale element
+    /// reference error for any element, the whole operation is retried from the start with a
+    /// fresh set of matches, for as long as the poller allows. This means the action may run
+    /// more than once for the same element, so it should be idempotent (e.g. only check a
+    /// checkbox if it is not already checked). Any other error is returned immediately.
+    ///
+    /// This is all-or-retry rather than best-effort: if elements keep going stale until the
+    /// poller times out, a Timeout error is returned rather than a partial count.
+    pub async fn each<F, Fut>(&self, f: F) -> WebDriverResult<usize>
+    where
+        F: Fn(WebElement<'a>) -> Fut,
+        Fut: Future<Output = WebDriverResult<()>>,
+    {
+        self.map_all(false, f).await.map(|x| x.len())
+    }
+
+    /// Get the text of every matching element, in the order the elements were matched.
+    ///
+    /// The matches are resolved as for `all()`, so this returns an empty Vec (rather than an
+    /// error) if no elements match before the poller times out. Use `texts_required()` to
+    /// return an error instead.
+    ///
+    /// NOTE: Staleness is not retried per element. A stale element cannot be found again on
+    /// its own, so if any element goes stale before its text has been read, the matches are
+    /// resolved again and the texts of all of them are read from the start, as for `each()`.
+    /// This keeps all texts from the same set of matches. Resolving the matches and retrying
+    /// share the query's poller, and if elements keep going stale until it times out, a
+    /// Timeout error is returned.
+    pub async fn texts(&self) -> WebDriverResult<Vec<String>> {
+        self.map_all(false, |elem| async move { elem.text().await }).await
+    }
+
+    /// Same as `texts()`, but the matches are resolved as for `all_required()`, so this returns
+    /// a NoSuchElement error if no elements match before the poller times out.
+    pub async fn texts_required(&self) -> WebDriverResult<Vec<String>> {
+        self.map_all(true, |elem| async move { elem.text().await }).await
+    }
+
+    /// Run the specified function against every matching element and collect the results,
+    /// retrying with a fresh set of matches if any element goes stale. See `each()`.
+    /// If `required` is true, the matches are resolved as for `all_required()`, otherwise as
+    /// for `all()`. Resolving the matches and retrying share a single poller.
+    async fn map_all<F, Fut, T>(&self, required: bool, f: F) -> WebDriverResult<Vec<T>>
+    where
+        F: Fn(WebElement<'a>) -> Fut,
+        Fut: Future<Output = WebDriverResult<T>>,
+    {
+        if self.selectors.is_empty() {
+            return Err(self.not_found_error());
+        }
+        let mut ticker = self.new_ticker()?;
+        let result = self.map_all_with_ticker(required, f, &mut ticker).await;
+        // Nothing matched if an empty Vec is returned.
+        self.notify_wait(&ticker, matches!(&result, Ok(x) if !x.is_empty()));
+        result
+    }
+
+    async fn map_all_with_ticker<F, Fut, T>(
+        &self,
+        required: bool,
+        f: F,
+        ticker: &mut ElementPollerTicker,
+    ) -> WebDriverResult<Vec<T>>
+    where
+        F: Fn(WebElement<'a>) -> Fut,
+        Fut: Future<Output = WebDriverResult<T>>,
+    {
+        let mut fallback_action = self.fallback_action.as_ref();
+        loop {
+            let check_start = Instant::now();
+            let mut last_error = None;
+            if let Some(elements) = self.poll_once(ticker.attempts() + 1, false).await? {
+                let mut results = Vec::with_capacity(elements.len());
+                for elem in elements {
+                    match f(elem).await {
+                        Ok(x) => results.push(x),
+                        Err(e @ WebDriverError::StaleElementReference(_)) => {
+                            last_error = Some(e);
+                            break;
+                        }
+                        Err(e) => return Err(e),
+                    }
+                }
+                if last_error.is_none() {
+                    return Ok(results);
+                }
+            }
+            ticker.record_check_duration(check_start.elapsed());
+
+            // As for `all()`, the fallback action only runs if nothing matched.
+            if last_error.is_none() {
+                if let Some(action) = fallback_action.take() {
+                    action().await?;
+                    ticker.reset();
+                    continue;
+                }
+            }
+
+            if !ticker.tick().await {
+                return match last_error {
+                    Some(e) => {
+                        let message = format!(
+                            "Elements kept going stale using selectors: {}",
+                            get_selector_summary(&self.selectors)
+                        );
+                        let err = self.wait_error(&message, ticker).await;
+                        Err(err.with_source(e).into())
+                    }
+                    None if required => Err(self.not_found_error_with_diagnostics().await),
+                    None => {
+                        self.save_timeout_source().await;
+                        Ok(Vec::new())
+                    }
+                };
+            }
+        }
+    }
+
     //
     // Helper Retrievers
     //
 
+    /// Get the error returned if no elements were found, using the timeout message if set.
+    fn not_found_error(&self) -> WebDriverError {
+        WebDriverError::NoSuchElement(WebDriverErrorInfo::new(&self.not_found_message()))
+    }
+
+    fn not_found_message(&self) -> String {
+        match &self.timeout_message {
+            Some(message) => message.clone(),
+            None => no_such_element_message(&self.selectors, &self.description),
+        }
+    }
+
+    /// Get the error returned if no element was found, including the selectors and the
+    /// beginning of the page source if `diagnostics()` is enabled.
+    async fn not_found_error_with_diagnostics(&self) -> WebDriverError {
+        if !self.diagnostics {
+            return self.not_found_error();
+        }
+        let mut message = self.not_found_message();
+        if self.timeout_message.is_some() {
+            // The default message already lists the selectors.
+            message
+                .push_str(&format!("\nSelectors tried: {}", get_selector_summary(&self.selectors)));
+        }
+        let source = match self.source.session().page_source().await {
+            Ok(source) => truncate_source(&source, DIAGNOSTIC_SOURCE_LEN),
+            Err(e) => format!("Page source unavailable: {}", e),
+        };
+        WebDriverError::NoSuchElement(WebDriverErrorInfo::new(&format!("{}\n{}", message, source)))
+    }
+
+    /// Get the error returned if the poller timed out, using the timeout message if set
+    /// instead of the specified message. This also saves the page source if
+    /// `source_on_timeout()` is enabled.
+    async fn wait_error(&self, message: &str, ticker: &ElementPollerTicker) -> WaitError {
+        self.save_timeout_source().await;
+        let message = self.timeout_message.as_deref().unwrap_or(message);
+        WaitError::new(message, ticker.attempts(), ticker.elapsed())
+    }
+
+    /// Save the page source if `source_on_timeout()` is enabled.
+    async fn save_timeout_source(&self) {
+        let dir = match &self.timeout_source_dir {
+            Some(x) => x,
+            None => return,
+        };
+        let path = timestamped_path(dir, &self.description, "query", "html");
+        let result = match self.source.session().page_source().await {
+            Ok(source) => std::fs::write(&path, source).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        #[cfg(feature = "log")]
+        {
+            if let Err(e) = result {
+                log::warn!("Failed to save page source to {}: {}", path.display(), e);
+            }
+        }
+        #[cfg(not(feature = "log"))]
+        {
+            let _ = result;
+        }
+    }
+
     /// Run the poller for this ElementQuery and return the Vec of WebElements matched.
     /// NOTE: This function doesn't return a no_such_element error and the caller must handle it.
     async fn run_poller(&self, inverted: bool) -> WebDriverResult<Vec<WebElement<'a>>> {
-        let no_such_element_error = no_such_element(&self.selectors, &self.description);
+        let no_such_element_error = self.not_found_error();
         if self.selectors.is_empty() {
             return Err(no_such_element_error);
         }
-        let mut ticker = ElementPollerTicker::new(self.poller.clone());
+        let mut ticker = self.new_ticker()?;
+        let result = self.poll_until_satisfied(inverted, &mut ticker).await;
 
+        if let Ok(None) = result {
+            self.save_timeout_source().await;
+        }
+        self.notify_wait(&ticker, matches!(result, Ok(Some(_))));
+        result.map(Option::unwrap_or_default)
+    }
+
+    /// Notify the observer (if any) that a wait for this query has finished. Every polling
+    /// loop of this query calls this once it is done, with the ticker it used.
+    fn notify_wait(&self, ticker: &ElementPollerTicker, success: bool) {
+        let description = if self.description.is_empty() {
+            get_selector_summary(&self.selectors)
+        } else {
+            self.description.clone()
+        };
+        notify_observer(&description, ticker, success);
+    }
+
+    /// Poll until the query is satisfied, and return the WebElements matched, or None if the
+    /// poller timed out.
+    async fn poll_until_satisfied(
+        &self,
+        inverted: bool,
+        ticker: &mut ElementPollerTicker,
+    ) -> WebDriverResult<Option<Vec<WebElement<'a>>>> {
+        let mut fallback_action = if inverted {
+            None
+        } else {
+            self.fallback_action.as_ref()
+        };
+        loop {
+            let check_start = Instant::now();
+            if let Some(elements) = self.poll_once(ticker.attempts() + 1, inverted).await? {
+                return Ok(Some(elements));
+            }
+            ticker.record_check_duration(check_start.elapsed());
+
+            if let Some(action) = fallback_action.take() {
+                action().await?;
+                // The elements are only expected to appear after the action, so give them
+                // the full timeout from here.
+                ticker.reset();
+                continue;
+            }
+
+            if !ticker.tick().await {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Run all selectors for this ElementQuery once, and return the WebElements matched
+    /// if the query was satisfied, otherwise None.
+    async fn poll_once(
+        &self,
+        attempt: u32,
+        inverted: bool,
+    ) -> WebDriverResult<Option<Vec<WebElement<'a>>>> {
         let check = |value: bool| {
             if inverted {
                 !value
@@ -288,27 +1683,102 @@ impl<'a> ElementQuery<'a> {
             }
         };
 
-        loop {
-            for selector in &self.selectors {
-                let mut elements = match self.fetch_elements_from_source(selector).await {
-                    Ok(x) => x,
-                    Err(WebDriverError::NoSuchElement(_)) => Vec::new(),
-                    Err(e) => return Err(e),
-                };
+        let mut combined = Vec::new();
+        for (_, selector) in self.prioritized_selectors() {
+            // A selector that failed with an error neither matches nor counts as empty.
+            let elements = match self.fetch_matches(selector, attempt).await? {
+                Some(x) => x,
+                None => continue,
+            };
+            if self.ordered && !inverted {
+                combined.extend(elements);
+            } else if check(!elements.is_empty()) {
+                return Ok(Some(elements));
+            }
+        }
 
-                if !elements.is_empty() {
-                    elements = selector.run_filters(elements).await?;
-                }
+        if !combined.is_empty() {
+            return self.sort_by_document_order(combined).await.map(Some);
+        }
 
-                if check(!elements.is_empty()) {
-                    return Ok(elements);
-                }
-            }
+        Ok(None)
+    }
 
-            if !ticker.tick().await {
-                return Ok(Vec::new());
+    /// Create a ticker for this query's poller.
+    fn new_ticker(&self) -> WebDriverResult<ElementPollerTicker> {
+        let mut ticker = session_ticker(self.source.session(), &self.poller)?;
+        ticker.set_on_poll(self.on_poll.clone());
+        Ok(ticker)
+    }
+
+    /// Get the selectors in the order they are checked within a poll, i.e. the preferred
+    /// selectors followed by all others, each in the order they were added, together with
+    /// their index in the order they were added. See `prefer()`.
+    fn prioritized_selectors(&self) -> impl Iterator<Item = (usize, &ElementSelector<'a>)> + '_ {
+        let preferred = self.selectors.iter().enumerate().filter(|(_, x)| x.preferred);
+        let others = self.selectors.iter().enumerate().filter(|(_, x)| !x.preferred);
+        preferred.chain(others)
+    }
+
+    /// Run the specified selector once, including its filters, and return the WebElements
+    /// matched, or None if an error occurred and was swallowed due to `retry_on_error()`.
+    async fn fetch_matches(
+        &self,
+        selector: &ElementSelector<'a>,
+        attempt: u32,
+    ) -> WebDriverResult<Option<Vec<WebElement<'a>>>> {
+        let result = self.fetch_elements_from_source(selector).await;
+        let mut elements = match handle_find_error(result, self.retry_on_error)? {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        let num_found = elements.len();
+
+        if !elements.is_empty() {
+            let result = selector.run_filters(elements).await;
+            elements = match handle_find_error(result, self.retry_on_error)? {
+                Some(x) => x,
+                None => return Ok(None),
+            };
+        }
+        self.log_count(attempt, selector, num_found, elements.len());
+        Ok(Some(elements))
+    }
+
+    /// Remove any duplicates from the specified elements, and sort them in document order.
+    async fn sort_by_document_order(
+        &self,
+        elements: Vec<WebElement<'a>>,
+    ) -> WebDriverResult<Vec<WebElement<'a>>> {
+        let mut args = ScriptArgs::new();
+        args.push(elements)?;
+        let ret = self
+            .source
+            .session()
+            .execute_script_with_args(SORT_BY_DOCUMENT_ORDER_SCRIPT, &args)
+            .await?;
+        ret.get_elements()
+    }
+
+    /// Log the number of elements matched by the specified selector, if enabled.
+    fn log_count(&self, attempt: u32, selector: &ElementSelector, found: usize, matched: usize) {
+        #[cfg(feature = "log")]
+        {
+            if self.log_counts {
+                log::debug!(
+                    "Query attempt {}: selector {} found {} element(s), {} after filters",
+                    attempt,
+                    selector.as_by(),
+                    found,
+                    matched
+                );
             }
         }
+
+        #[cfg(not(feature = "log"))]
+        {
+            let _ = (self.log_counts, attempt, selector, found, matched);
+        }
     }
 
     /// Execute the specified selector and return any matched WebElements.
@@ -316,10 +1786,46 @@ impl<'a> ElementQuery<'a> {
         &self,
         selector: &ElementSelector<'a>,
     ) -> impl Future<Output = WebDriverResult<Vec<WebElement<'a>>>> + Send {
-        let by = selector.by.clone();
+        let from_element = match self.source.as_ref() {
+            ElementQuerySource::Driver(_) => self.root_by.is_some(),
+            ElementQuerySource::Element(_) => true,
+        };
+        let by = selector.selector_by();
+        let relative = match from_element && self.relative_xpath {
+            true => by.to_relative_xpath(),
+            false => None,
+        };
+        let by = relative.unwrap_or(by);
         let single = selector.single;
         let source = self.source.clone();
+        let root_by = self.root_by.clone();
+        let reuse_root = self.assume_stable_root;
+        let root_cache = self.root_cache.clone();
         async move {
+            if let Some(root_by) = root_by {
+                let resolve = || async {
+                    match source.as_ref() {
+                        ElementQuerySource::Driver(driver) => {
+                            driver.find_element(root_by.as_by()).await
+                        }
+                        ElementQuerySource::Element(element) => {
+                            element.find_element(root_by.as_by()).await
+                        }
+                    }
+                };
+                let find = |root: WebElement<'a>| {
+                    let by = by.clone();
+                    async move {
+                        match single {
+                            true => root.find_element(by.as_by()).await.map(|x| vec![x]),
+                            false => root.find_elements(by.as_by()).await,
+                        }
+                    }
+                };
+                return find_under_root(&root_cache, reuse_root, resolve, find).await;
+            }
+
+            let by = by.as_by();
             match single {
                 true => match source.as_ref() {
                     ElementQuerySource::Driver(driver) => {
@@ -693,28 +2199,73 @@ impl<'a> ElementQuery<'a> {
             ignore_errors,
         ))
     }
+
+    /// Only match elements that contain at least one descendant matching the specified
+    /// selector, e.g. `query(By::Css("div")).having_descendant(By::Css(".active"))` instead of
+    /// `div:has(.active)`, which is not supported by all browsers.
+    /// See `conditions::element_has_descendant()` for details.
+    ///
+    /// This is evaluated as a filter on each poll iteration, in the order filters were added,
+    /// so it composes with any other filters on the same selector. Each element requires an
+    /// additional request, so add cheaper filters first where possible. An element that goes
+    /// stale while being filtered is handled according to `ignore_errors()`, i.e. by default it
+    /// is dropped from the matches and the query continues polling.
+    pub fn having_descendant<B>(self, by: B) -> Self
+    where
+        B: Into<SelectorBy<'static>>,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.with_filter(conditions::element_has_descendant(by, ignore_errors))
+    }
 }
 
 /// Trait for enabling the ElementQuery interface.
 pub trait ElementQueryable {
     fn query<'a>(&'a self, by: By<'a>) -> ElementQuery<'a>;
+
+    /// Return an ElementQuery instance matching elements with the specified test id.
+    ///
+    /// By default this matches the `data-testid` attribute. To use a different attribute,
+    /// set the "TestIdAttribute" key in the session config, e.g.
+    /// `driver.config_mut().set("TestIdAttribute", "data-test")?;`
+    fn query_testid<'a>(&'a self, test_id: &str) -> ElementQuery<'a>;
 }
 
 impl ElementQueryable for WebElement<'_> {
     /// Return an ElementQuery instance for more executing powerful element queries.
     fn query<'a>(&'a self, by: By<'a>) -> ElementQuery<'a> {
-        let poller: ElementPoller =
-            self.session.config().get("ElementPoller").unwrap_or(ElementPoller::NoWait);
-        ElementQuery::new(ElementQuerySource::Element(&self), poller, by)
+        let poller = default_poller(self.session);
+        ElementQuery::new(
+            ElementQuerySource::Element(&self),
+            poller,
+            vec![ElementSelector::new(by)],
+        )
+    }
+
+    /// Return an ElementQuery instance matching elements with the specified test id.
+    fn query_testid<'a>(&'a self, test_id: &str) -> ElementQuery<'a> {
+        let poller = default_poller(self.session);
+        let selector = ElementSelector::owned(SelectorBy::Css(test_id_css(self.session, test_id)));
+        ElementQuery::new(ElementQuerySource::Element(&self), poller, vec![selector])
     }
 }
 
 impl ElementQueryable for WebDriver {
     /// Return an ElementQuery instance for more executing powerful element queries.
     fn query<'a>(&'a self, by: By<'a>) -> ElementQuery<'a> {
-        let poller: ElementPoller =
-            self.config().get("ElementPoller").unwrap_or(ElementPoller::NoWait);
-        ElementQuery::new(ElementQuerySource::Driver(&self.session), poller, by)
+        let poller = default_poller(&self.session);
+        ElementQuery::new(
+            ElementQuerySource::Driver(&self.session),
+            poller,
+            vec![ElementSelector::new(by)],
+        )
+    }
+
+    /// Return an ElementQuery instance matching elements with the specified test id.
+    fn query_testid<'a>(&'a self, test_id: &str) -> ElementQuery<'a> {
+        let poller = default_poller(&self.session);
+        let selector = ElementSelector::owned(SelectorBy::Css(test_id_css(&self.session, test_id)));
+        ElementQuery::new(ElementQuerySource::Driver(&self.session), poller, vec![selector])
     }
 }
 
@@ -738,10 +2289,123 @@ async fn _test_is_send() -> WebDriverResult<()> {
     // ElementQuery
     let query = driver.query(By::Css("div"));
     is_send_val(&query.exists());
+    is_send_val(&query.appears());
     is_send_val(&query.not_exists());
+    is_send_val(&query.none());
     is_send_val(&query.first());
+    is_send_val(&query.clone().having_descendant(By::Css(".active")).first());
+    is_send_val(&query.clone().source_on_timeout("sources").first());
+    is_send_val(&query.clone().or_else_action(|| async { Ok(()) }).first());
+    is_send_val(&query.first_with_selector());
+    is_send_val(&query.first_wait_until("ready"));
+    is_send_val(&query.first_then_wait(conditions::element_is_clickable(true)));
+    is_send_val(&query.first_select());
+    let mut cache = None;
+    is_send_val(&query.first_cached(&mut cache));
+    is_send_val(&query.clone().diagnostics(true).first());
+    is_send_val(&query.with_root_selector(By::Id("main")).assume_stable_root(true).first());
+    is_send_val(&query.race());
     is_send_val(&query.all());
     is_send_val(&query.all_required());
+    is_send_val(&query.count_stable(3));
+    is_send_val(&query.count_decreased_by(1));
+    is_send_val(&query.count_matches(|n| n % 2 == 0));
+    is_send_val(&query.assert_count(3));
+    is_send_val(&query.selected_count(2));
+    is_send_val(&query.count_checked_at_least(1));
+    is_send_val(&query.each(|elem| async move { elem.click().await }));
+    is_send_val(&query.texts());
+    is_send_val(&query.texts_required());
+    let roots = driver.find_elements(By::Css("section")).await?;
+    is_send_val(&query.under_all(&roots));
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_xpath() {
+        let relative = |by: By<'static>| SelectorBy::from(by).to_relative_xpath();
+        assert_eq!(relative(By::XPath("//div")).unwrap().to_string(), "XPath(.//div)");
+        assert_eq!(relative(By::XPath("(//div)[1]")).unwrap().to_string(), "XPath((.//div)[1])");
+        assert!(relative(By::XPath(".//div")).is_none());
+        assert!(relative(By::XPath("/html/body")).is_none());
+        assert!(relative(By::Css("div")).is_none());
+    }
+
+    #[test]
+    fn test_find_errors_are_returned_by_default() {
+        let invalid = || Err(WebDriverError::CustomError(String::from("invalid selector")));
+        assert!(handle_find_error::<()>(invalid(), DEFAULT_RETRY_ON_ERROR).is_err());
+
+        let not_found = Err(WebDriverError::NoSuchElement(WebDriverErrorInfo::new("not found")));
+        let result = handle_find_error::<()>(not_found, DEFAULT_RETRY_ON_ERROR).unwrap();
+        assert_eq!(result, Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_retried_errors_are_not_empty_results() {
+        // A swallowed error must not look like an empty result, otherwise negated waits
+        // such as none() would succeed on errors.
+        let invalid = Err(WebDriverError::CustomError(String::from("stale root")));
+        assert_eq!(handle_find_error::<()>(invalid, true).unwrap(), None);
+    }
+
+    #[test]
+    fn test_find_under_root() {
+        // Roots are represented by a generation number, and roots older than the current
+        // generation are stale.
+        let generation = Mutex::new(1u32);
+        let resolves = Mutex::new(0u32);
+        let resolve = || {
+            *resolves.lock().unwrap() += 1;
+            let root = *generation.lock().unwrap();
+            async move { Ok(root) }
+        };
+        let find = |root: u32| {
+            let current = *generation.lock().unwrap();
+            async move {
+                match root == current {
+                    true => Ok(root),
+                    false => Err(WebDriverError::StaleElementReference(WebDriverErrorInfo::new(
+                        "stale element reference",
+                    ))),
+                }
+            }
+        };
+        let run = |cache: &Mutex<Option<u32>>, reuse| {
+            futures::executor::block_on(find_under_root(cache, reuse, resolve, find)).unwrap()
+        };
+
+        // By default, the root is resolved on every find.
+        let cache = Mutex::new(None);
+        assert_eq!(run(&cache, false), 1);
+        assert_eq!(run(&cache, false), 1);
+        assert_eq!(*resolves.lock().unwrap(), 2);
+        assert!(cache.lock().unwrap().is_none());
+
+        // A stable root is resolved once and then reused.
+        *resolves.lock().unwrap() = 0;
+        assert_eq!(run(&cache, true), 1);
+        assert_eq!(run(&cache, true), 1);
+        assert_eq!(*resolves.lock().unwrap(), 1);
+
+        // A stale root is resolved again, and the find is retried from the new root.
+        *generation.lock().unwrap() = 2;
+        assert_eq!(run(&cache, true), 2);
+        assert_eq!(*resolves.lock().unwrap(), 2);
+        assert_eq!(*cache.lock().unwrap(), Some(2));
+    }
+
+    #[test]
+    fn test_truncate_source() {
+        assert_eq!(truncate_source("<html></html>", 20), "Page source:\n<html></html>");
+        assert_eq!(
+            truncate_source("<p>héllo</p>", 5),
+            "Page source (first 5 of 12 characters):\n<p>hé"
+        );
+    }
+}
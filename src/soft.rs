@@ -0,0 +1,79 @@
+use std::sync::{Arc, Mutex};
+use thirtyfour::error::WebDriverError;
+use thirtyfour::prelude::WebDriverResult;
+
+/// A collector for failed waits, for tests that should continue after a failed wait and report
+/// all failures at the end. See `ElementWaiter::soft()`.
+///
+/// ```ignore
+/// let soft = SoftAssertions::new();
+/// header.wait_until().soft(&soft).error("Header not shown").displayed().await?;
+/// footer.wait_until().soft(&soft).error("Footer not shown").displayed().await?;
+/// soft.fail_if_any()?;
+/// ```
+///
+/// Clones share the same failures, so a clone can be passed to other tasks or helpers.
+#[derive(Debug, Clone, Default)]
+pub struct SoftAssertions {
+    failures: Arc<Mutex<Vec<String>>>,
+}
+
+impl SoftAssertions {
+    /// Create a new, empty SoftAssertions collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a failure. Waits using this collector call this with their timeout message,
+    /// but it can also be used to record failures of other checks.
+    pub fn record(&self, message: &str) {
+        self.failures.lock().unwrap().push(message.to_string());
+    }
+
+    /// Get the messages of all failures recorded so far, in the order they were recorded.
+    pub fn failures(&self) -> Vec<String> {
+        self.failures.lock().unwrap().clone()
+    }
+
+    /// Return true if no failures have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.failures.lock().unwrap().is_empty()
+    }
+
+    /// Return `WebDriverError::CustomError` listing all recorded failures, if there are any.
+    /// This is intended to be called once at the end of the test.
+    pub fn fail_if_any(&self) -> WebDriverResult<()> {
+        let failures = self.failures.lock().unwrap();
+        if failures.is_empty() {
+            return Ok(());
+        }
+        let mut message = format!("{} soft assertion(s) failed:", failures.len());
+        for failure in failures.iter() {
+            message.push_str("\n  - ");
+            message.push_str(failure);
+        }
+        Err(WebDriverError::CustomError(message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fail_if_any() {
+        let soft = SoftAssertions::new();
+        assert!(soft.fail_if_any().is_ok());
+
+        soft.clone().record("Header not shown");
+        soft.record("Footer not shown");
+        assert_eq!(soft.failures().len(), 2);
+        match soft.fail_if_any() {
+            Err(WebDriverError::CustomError(message)) => {
+                assert!(message.starts_with("2 soft assertion(s) failed:"));
+                assert!(message.contains("\n  - Header not shown\n  - Footer not shown"));
+            }
+            x => panic!("unexpected result: {:?}", x),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use crate::poller::default_poller;
+use crate::{ElementPoller, ElementPredicate, ElementQuery, ElementQuerySource, ElementSelector};
+use thirtyfour::prelude::WebDriver;
+use thirtyfour::{By, WebElement};
+
+/// An owned, reusable definition of a query, e.g. for use as a field in a page object.
+///
+/// `ElementQuery` borrows the driver or element it queries from, which makes it awkward to
+/// store. A `QuerySpec` instead owns its selectors, filters and settings, and can be resolved
+/// into an `ElementQuery` whenever it is needed, using `resolve()` (from the driver) or
+/// `resolve_from()` (from an element). The same spec can be resolved any number of times.
+///
+/// Since filters are boxed closures, a `QuerySpec` cannot be a `const`. Instead, construct it
+/// in a function or in the constructor of your page object.
+///
+/// # Example:
+/// ```ignore
+/// let search = QuerySpec::new(By::Id("search")).or(By::Css("input[name='q']")).desc("search box");
+/// let elem = search.resolve(&driver).first().await?;
+/// ```
+#[derive(Clone)]
+pub struct QuerySpec {
+    selectors: Vec<ElementSelector<'static>>,
+    poller: Option<ElementPoller>,
+    ignore_errors: bool,
+    description: String,
+}
+
+impl QuerySpec {
+    pub fn new(by: By<'static>) -> Self {
+        Self {
+            selectors: vec![ElementSelector::new(by)],
+            poller: None,
+            ignore_errors: true,
+            description: String::new(),
+        }
+    }
+
+    /// Add a new selector to this QuerySpec. All filters specified after this selector
+    /// (up until the next `or()` method) will apply to this selector.
+    pub fn or(mut self, by: By<'static>) -> Self {
+        self.selectors.push(ElementSelector::new(by));
+        self
+    }
+
+    /// Add the specified ElementPredicate to the last selector.
+    /// See the `conditions` module for the predicates available.
+    pub fn with_filter(mut self, f: ElementPredicate) -> Self {
+        if let Some(selector) = self.selectors.last_mut() {
+            selector.add_filter(f);
+        }
+        self
+    }
+
+    /// Provide a name that will be included in the error message if the query was not
+    /// successful.
+    pub fn desc(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    /// Set whether errors are ignored while polling. See `ElementQuery::ignore_errors()`.
+    pub fn ignore_errors(mut self, ignore: bool) -> Self {
+        self.ignore_errors = ignore;
+        self
+    }
+
+    /// Use the specified ElementPoller for queries resolved from this QuerySpec, rather than
+    /// the default ElementPoller of the driver.
+    pub fn with_poller(mut self, poller: ElementPoller) -> Self {
+        self.poller = Some(poller);
+        self
+    }
+
+    /// Resolve this QuerySpec into an ElementQuery that queries from the specified driver.
+    pub fn resolve<'a>(&self, driver: &'a WebDriver) -> ElementQuery<'a> {
+        let poller = self.poller.clone().unwrap_or_else(|| default_poller(&driver.session));
+        self.build(ElementQuerySource::Driver(&driver.session), poller)
+    }
+
+    /// Resolve this QuerySpec into an ElementQuery that queries from the specified element.
+    pub fn resolve_from<'a>(&self, element: &'a WebElement<'a>) -> ElementQuery<'a> {
+        let poller = self.poller.clone().unwrap_or_else(|| default_poller(element.session));
+        self.build(ElementQuerySource::Element(element), poller)
+    }
+
+    fn build<'a>(&self, source: ElementQuerySource<'a>, poller: ElementPoller) -> ElementQuery<'a> {
+        ElementQuery::new(source, poller, self.selectors.clone())
+            .desc(&self.description)
+            .ignore_errors(self.ignore_errors)
+    }
+}
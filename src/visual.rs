@@ -0,0 +1,231 @@
+use crate::conditions::element_script_value;
+use image::{imageops, ImageFormat, ImageOutputFormat, Rgba, RgbaImage};
+use serde_json::Value;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use thirtyfour::error::{WebDriverError, WebDriverResult};
+use thirtyfour::{WebDriverCommands, WebElement};
+
+/// The result of comparing an image against a baseline image.
+pub(crate) struct ImageDiff {
+    /// The fraction of pixels that differ, between 0 and 1.
+    pub ratio: f64,
+    /// An image highlighting the differing pixels in red, or None if the dimensions differ.
+    pub diff: Option<RgbaImage>,
+}
+
+/// Compare the specified image against the baseline, pixel by pixel. A pixel differs if any
+/// of its channels (including alpha) differs. Images with different dimensions are treated
+/// as differing in every pixel.
+pub(crate) fn compare_images(actual: &RgbaImage, baseline: &RgbaImage) -> ImageDiff {
+    if actual.dimensions() != baseline.dimensions() {
+        return ImageDiff {
+            ratio: 1.0,
+            diff: None,
+        };
+    }
+
+    let (width, height) = actual.dimensions();
+    let mut diff = RgbaImage::new(width, height);
+    let mut num_differing: u64 = 0;
+    for (x, y, pixel) in actual.enumerate_pixels() {
+        if pixel == baseline.get_pixel(x, y) {
+            // Keep matching pixels as a faded grayscale image for context.
+            let Rgba([r, g, b, _]) = *pixel;
+            let luma = ((r as u16 + g as u16 + b as u16) / 3) as u8;
+            diff.put_pixel(x, y, Rgba([luma, luma, luma, 64]));
+        } else {
+            num_differing += 1;
+            diff.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        }
+    }
+
+    let num_pixels = width as u64 * height as u64;
+    let ratio = if num_pixels == 0 {
+        0.0
+    } else {
+        num_differing as f64 / num_pixels as f64
+    };
+    ImageDiff {
+        ratio,
+        diff: Some(diff),
+    }
+}
+
+/// Decode the specified PNG data.
+pub(crate) fn decode_png(data: &[u8]) -> WebDriverResult<RgbaImage> {
+    image::load_from_memory_with_format(data, ImageFormat::Png)
+        .map(|x| x.to_rgba8())
+        .map_err(|e| WebDriverError::CustomError(format!("Failed to decode PNG image: {}", e)))
+}
+
+/// Encode the specified image as PNG.
+pub(crate) fn encode_png(image: &RgbaImage) -> WebDriverResult<Vec<u8>> {
+    let mut data = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut data), ImageOutputFormat::Png)
+        .map_err(|e| WebDriverError::CustomError(format!("Failed to encode PNG image: {}", e)))?;
+    Ok(data)
+}
+
+/// A rectangular region of an image, in image pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Region {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Get the region of a screenshot covering the specified rect (x, y, width and height in CSS
+/// pixels, relative to the viewport), extended by `padding` CSS pixels on each side. The rect
+/// is scaled by the device pixel ratio, rounded outwards to whole pixels and clamped to the
+/// dimensions of the screenshot. Returns None if nothing of the region is inside the
+/// screenshot.
+pub(crate) fn padded_region(
+    rect: (f64, f64, f64, f64),
+    padding: f64,
+    device_pixel_ratio: f64,
+    dimensions: (u32, u32),
+) -> Option<Region> {
+    let (x, y, width, height) = rect;
+    let (max_x, max_y) = (dimensions.0 as f64, dimensions.1 as f64);
+    let left = ((x - padding) * device_pixel_ratio).floor().max(0.0);
+    let top = ((y - padding) * device_pixel_ratio).floor().max(0.0);
+    let right = ((x + width + padding) * device_pixel_ratio).ceil().min(max_x);
+    let bottom = ((y + height + padding) * device_pixel_ratio).ceil().min(max_y);
+    if right <= left || bottom <= top {
+        return None;
+    }
+    Some(Region {
+        x: left as u32,
+        y: top as u32,
+        width: (right - left) as u32,
+        height: (bottom - top) as u32,
+    })
+}
+
+/// Script that scrolls the element into view if needed, and returns its rect relative to the
+/// viewport along with the device pixel ratio.
+const VIEWPORT_RECT_SCRIPT: &str = r#"
+    const elem = arguments[0];
+    elem.scrollIntoView({block: "nearest", inline: "nearest"});
+    const rect = elem.getBoundingClientRect();
+    return [rect.x, rect.y, rect.width, rect.height, window.devicePixelRatio || 1];
+"#;
+
+/// Capture a PNG screenshot of the element along with `padding` CSS pixels of its surroundings
+/// on each side, e.g. to include some context when saving or comparing screenshots for visual
+/// tests. This requires the `visual` feature.
+///
+/// WebDriver screenshots only cover the viewport, so the element is first scrolled into view
+/// if needed (via `scrollIntoView()` with `block` and `inline` set to `nearest`). The
+/// element's rect is then read via `getBoundingClientRect()` and multiplied by
+/// `window.devicePixelRatio` to get screenshot pixels, since the screenshot is taken at the
+/// device resolution. The region is rounded outwards to whole pixels and clamped to the
+/// bounds of the screenshot, so the padding is smaller (or missing) on sides where the element
+/// touches the edge of the viewport, and an element larger than the viewport is cut off.
+///
+/// NOTE: Browser zoom and CSS transforms on the document may cause the rect not to line up
+/// exactly with the screenshot. Also note that the screenshot includes anything overlapping
+/// the element, e.g. sticky headers.
+pub async fn screenshot_with_padding(
+    elem: &WebElement<'_>,
+    padding: f64,
+) -> WebDriverResult<Vec<u8>> {
+    let value = element_script_value(elem, VIEWPORT_RECT_SCRIPT).await?;
+    let numbers: Vec<f64> = match value.as_array() {
+        Some(x) => x.iter().filter_map(Value::as_f64).collect(),
+        None => Vec::new(),
+    };
+    let (rect, device_pixel_ratio) = match numbers.as_slice() {
+        [x, y, width, height, dpr] => ((*x, *y, *width, *height), *dpr),
+        _ => {
+            return Err(WebDriverError::CustomError(format!(
+                "Failed to read element rect: {}",
+                value
+            )))
+        }
+    };
+
+    let screenshot = decode_png(&elem.session.screenshot_as_png().await?)?;
+    match padded_region(rect, padding, device_pixel_ratio, screenshot.dimensions()) {
+        Some(r) => {
+            encode_png(&imageops::crop_imm(&screenshot, r.x, r.y, r.width, r.height).to_image())
+        }
+        None => Err(WebDriverError::CustomError(String::from(
+            "Element is outside the visible area of the screenshot",
+        ))),
+    }
+}
+
+/// Get the path the diff image is saved to for the specified baseline image, i.e. the
+/// baseline path with the extension replaced by `diff.png`.
+pub(crate) fn diff_path(baseline: &Path) -> PathBuf {
+    baseline.with_extension("diff.png")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_images() {
+        let white = Rgba([255, 255, 255, 255]);
+        let baseline = RgbaImage::from_pixel(2, 2, white);
+        assert_eq!(compare_images(&baseline, &baseline).ratio, 0.0);
+
+        let mut actual = baseline.clone();
+        actual.put_pixel(1, 0, Rgba([0, 0, 0, 255]));
+        let diff = compare_images(&actual, &baseline);
+        assert_eq!(diff.ratio, 0.25);
+        assert_eq!(diff.diff.unwrap().get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+
+        let larger = RgbaImage::from_pixel(3, 2, white);
+        let diff = compare_images(&larger, &baseline);
+        assert_eq!(diff.ratio, 1.0);
+        assert!(diff.diff.is_none());
+    }
+
+    #[test]
+    fn test_padded_region() {
+        let region = padded_region((10.0, 20.0, 30.0, 40.0), 5.0, 1.0, (100, 100));
+        assert_eq!(
+            region,
+            Some(Region {
+                x: 5,
+                y: 15,
+                width: 40,
+                height: 50
+            })
+        );
+
+        // Scaled by the device pixel ratio, and clamped to the screenshot.
+        let region = padded_region((2.0, 40.0, 30.0, 20.0), 5.0, 2.0, (100, 100));
+        assert_eq!(
+            region,
+            Some(Region {
+                x: 0,
+                y: 70,
+                width: 74,
+                height: 30
+            })
+        );
+
+        assert_eq!(padded_region((200.0, 0.0, 10.0, 10.0), 5.0, 1.0, (100, 100)), None);
+    }
+
+    #[test]
+    fn test_encode_png() {
+        let image = RgbaImage::from_pixel(3, 2, Rgba([1, 2, 3, 255]));
+        assert_eq!(decode_png(&encode_png(&image).unwrap()).unwrap(), image);
+    }
+
+    #[test]
+    fn test_diff_path() {
+        assert_eq!(
+            diff_path(Path::new("baselines/logo.png")),
+            Path::new("baselines/logo.diff.png")
+        );
+    }
+}
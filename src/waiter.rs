@@ -1,26 +1,210 @@
-use crate::conditions::handle_errors;
-use crate::{conditions, ElementPoller, ElementPollerTicker, ElementPredicate};
-use std::time::Duration;
-use stringmatch::Needle;
+use crate::conditions::{handle_errors, OverflowAxis, ReadyOptions};
+use crate::observer::notify_observer;
+use crate::poller::{default_poller, profile_poller, session_ticker, PollCallback};
+use crate::{
+    conditions, ElementPoller, ElementPollerTicker, ElementPredicate, ElementQueryable, SelectorBy,
+    SoftAssertions, WaitError,
+};
+use futures::future::{select, Either};
+use futures::Future;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use stringmatch::{Needle, StringMatch};
 use thirtyfour::error::WebDriverError;
 use thirtyfour::prelude::WebDriverResult;
-use thirtyfour::WebElement;
+use thirtyfour::support::sleep;
+use thirtyfour::{By, ElementRect, WebDriverCommands, WebElement};
 
+/// Run the specified check, giving up if it does not complete within the specified timeout.
+/// Returns None if the check timed out.
+async fn check_with_timeout<F>(check: F, timeout: Duration) -> Option<WebDriverResult<bool>>
+where
+    F: Future<Output = WebDriverResult<bool>> + Unpin,
+{
+    match select(check, Box::pin(sleep(timeout))).await {
+        Either::Left((result, _)) => Some(result),
+        Either::Right(_) => None,
+    }
+}
+
+/// Get the path of a file in the specified directory, named after the specified name (with
+/// any characters other than ASCII letters and digits replaced by underscores, or the default
+/// name if empty) and the current timestamp in milliseconds.
+pub(crate) fn timestamped_path(dir: &Path, name: &str, default: &str, extension: &str) -> PathBuf {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let timestamp =
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|x| x.as_millis()).unwrap_or(0);
+    let name = if name.is_empty() {
+        default
+    } else {
+        &name
+    };
+    dir.join(format!("{}-{}.{}", name, timestamp, extension))
+}
+
+/// The state of an element at a single point in time, as returned by
+/// `ElementWaiter::snapshot()`. If the element is not present, all other states are None,
+/// and each state is also None if reading it failed.
+///
+/// The attributes are only read for consistent snapshots (see
+/// `ElementWaiter::consistent_snapshot()`), and are None otherwise.
+#[derive(Debug, Clone)]
+pub struct ElementStateSnapshot {
+    pub present: bool,
+    pub displayed: Option<bool>,
+    pub enabled: Option<bool>,
+    pub selected: Option<bool>,
+    pub text: Option<String>,
+    pub rect: Option<ElementRect>,
+    pub attributes: Option<BTreeMap<String, String>>,
+}
+
+impl ElementStateSnapshot {
+    /// A snapshot of an element that is not present.
+    pub(crate) fn missing() -> Self {
+        Self {
+            present: false,
+            displayed: None,
+            enabled: None,
+            selected: None,
+            text: None,
+            rect: None,
+            attributes: None,
+        }
+    }
+}
+
+/// The outcome of `ElementWaiter::outcome()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitOutcome {
+    /// The success predicate returned true.
+    Success,
+    /// The failure predicate returned true.
+    Failure,
+    /// Neither predicate returned true before the poller timed out.
+    TimedOut,
+}
+
+/// A check against a snapshot of an element's state. See `ElementWaiterChain`.
+type SnapshotCheck = Box<dyn Fn(&ElementStateSnapshot) -> bool + Send + Sync>;
+
+/// Get a snapshot check that requires the element to be present, and the specified function
+/// to return true for its snapshot.
+fn snapshot_state<F>(f: F) -> SnapshotCheck
+where
+    F: Fn(&ElementStateSnapshot) -> bool + Send + Sync + 'static,
+{
+    Box::new(move |s| s.present && f(s))
+}
+
+/// Return true if the snapshot is of a clickable element, i.e. one that is displayed and
+/// enabled.
+fn is_clickable_snapshot(s: &ElementStateSnapshot) -> bool {
+    s.displayed == Some(true) && s.enabled == Some(true)
+}
+
+/// Get a snapshot check for whether the text matches (or does not match) the needle.
+fn snapshot_text<N>(text: N, matches: bool) -> SnapshotCheck
+where
+    N: Needle + Send + Sync + 'static,
+{
+    snapshot_state(move |s| s.text.as_deref().is_some_and(|x| text.is_match(x) == matches))
+}
+
+/// Get a snapshot check for whether the specified attribute matches (or does not match) the
+/// needle. A missing attribute does not match.
+fn snapshot_attribute<N>(name: &str, value: N, matches: bool) -> SnapshotCheck
+where
+    N: Needle + Send + Sync + 'static,
+{
+    let name = name.to_string();
+    snapshot_state(move |s| match &s.attributes {
+        Some(attributes) => attributes.get(&name).is_some_and(|x| value.is_match(x)) == matches,
+        None => false,
+    })
+}
+
+/// A condition that makes a wait fail immediately. See `ElementWaiter::or_fail_if()`.
+#[derive(Clone)]
+struct FailCondition {
+    predicate: Arc<ElementPredicate>,
+    message: String,
+}
+
+impl fmt::Debug for FailCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FailCondition").field("message", &self.message).finish()
+    }
+}
+
+/// High-level interface for waiting until an element satisfies one or more conditions.
+///
+/// The condition methods borrow the waiter rather than consuming it, so a waiter configured
+/// with a custom poller, error message and error handling can be reused for multiple checks.
 #[derive(Debug, Clone)]
 pub struct ElementWaiter<'a> {
-    element: &'a WebElement<'a>,
+    element: Cow<'a, WebElement<'a>>,
     poller: ElementPoller,
     message: String,
     ignore_errors: bool,
+    refetch_by: Option<By<'a>>,
+    per_check_timeout: Option<Duration>,
+    min_wait: Option<Duration>,
+    max_checks: Option<u32>,
+    required_hits: usize,
+    jitter: Option<f64>,
+    skip: bool,
+    fail_conditions: Vec<FailCondition>,
+    on_poll: Option<PollCallback>,
+    consistent_snapshot: bool,
+    scroll_into_view: bool,
+    success_screenshot_dir: Option<PathBuf>,
+    soft: Option<SoftAssertions>,
 }
 
 impl<'a> ElementWaiter<'a> {
     fn new(element: &'a WebElement<'a>, poller: ElementPoller) -> Self {
+        Self::with_element(Cow::Borrowed(element), poller)
+    }
+
+    /// Create an ElementWaiter that owns the element, for elements that are not borrowed
+    /// from elsewhere, e.g. the result of a query. See `ElementQuery::first_wait_until()`.
+    pub(crate) fn owned(element: WebElement<'a>, poller: ElementPoller) -> Self {
+        Self::with_element(Cow::Owned(element), poller)
+    }
+
+    fn with_element(element: Cow<'a, WebElement<'a>>, poller: ElementPoller) -> Self {
         Self {
             element,
             poller,
             message: String::new(),
             ignore_errors: true,
+            refetch_by: None,
+            per_check_timeout: None,
+            min_wait: None,
+            max_checks: None,
+            required_hits: 1,
+            jitter: None,
+            skip: false,
+            fail_conditions: Vec::new(),
+            on_poll: None,
+            consistent_snapshot: false,
+            scroll_into_view: false,
+            success_screenshot_dir: None,
+            soft: None,
         }
     }
 
@@ -45,6 +229,250 @@ impl<'a> ElementWaiter<'a> {
         self
     }
 
+    /// Re-resolve the element using the specified selector if it becomes stale while polling,
+    /// and continue polling using the new element. This is useful for UIs that re-render
+    /// the element while you are waiting for it.
+    ///
+    /// The selector is always executed from the driver, not relative to any other element,
+    /// so it must be absolute (e.g. use `//div` rather than `.//div` for XPath).
+    ///
+    /// NOTE: While this is enabled, each unsuccessful poll will also check whether the element
+    /// is still present, which requires an additional request.
+    pub fn refetch_on_stale(mut self, by: By<'a>) -> Self {
+        self.refetch_by = Some(by);
+        self
+    }
+
+    /// Limit the time that each individual condition check may take. If a check does not
+    /// complete within this time, e.g. because a WebDriver request hangs, it is abandoned so
+    /// that it does not block the rest of the poll iteration.
+    ///
+    /// When ignoring errors (the default), a check that times out is treated as the condition
+    /// not being satisfied for that poll iteration, and polling continues. Otherwise a
+    /// `Timeout` error is returned.
+    pub fn per_check_timeout(mut self, timeout: Duration) -> Self {
+        self.per_check_timeout = Some(timeout);
+        self
+    }
+
+    /// Do not accept success until at least the specified duration has elapsed since the wait
+    /// started. If the condition(s) are met before then, the waiter sleeps for the remaining
+    /// time and then checks the condition(s) again, continuing to poll as normal if they are
+    /// no longer met. This avoids accepting a state that matches "too early", e.g. the old
+    /// DOM before a re-render.
+    ///
+    /// The minimum wait takes precedence over the poller's timeout, so if it is longer than
+    /// the timeout, the condition(s) will still be checked once after the minimum wait.
+    pub fn min_wait(mut self, duration: Duration) -> Self {
+        self.min_wait = Some(duration);
+        self
+    }
+
+    /// Check the condition(s) at most the specified number of times, regardless of how much
+    /// time the poller allows. This is useful for expensive conditions, such as comparing
+    /// screenshots. If the condition(s) are not met after this many checks, a Timeout error
+    /// is returned.
+    ///
+    /// Both this limit and the poller's timeout apply, and whichever is reached first ends the
+    /// wait. For example, with `TimeoutWithInterval(30s, 1s)` and `max_checks(5)`, the wait
+    /// gives up after 5 checks (about 4 seconds) unless the checks themselves are slow enough
+    /// for the 30 second timeout to be reached first. A limit of 0 is treated as 1.
+    pub fn max_checks(mut self, checks: u32) -> Self {
+        self.max_checks = Some(checks);
+        self
+    }
+
+    /// Do not accept success until the condition(s) have been observed to be met on the
+    /// specified number of checks. The checks do not need to be consecutive, so a condition
+    /// that briefly flickers back to false does not reset the count. This debounces flaky
+    /// states without relying on timing, unlike `min_wait()`, which only requires that enough
+    /// time has passed.
+    ///
+    /// The checks still happen once per poll interval, and the poller's timeout still applies,
+    /// so the timeout must allow for at least `n` poll iterations, otherwise a Timeout error is
+    /// returned even though the condition(s) were met. A count of 0 is treated as 1.
+    pub fn require_hits(mut self, n: usize) -> Self {
+        self.required_hits = n;
+        self
+    }
+
+    /// Randomize each sleep between checks by up to the specified fraction of its length, in
+    /// either direction, regardless of which poller is used. This is useful for spreading out
+    /// many concurrent waits, e.g. across parallel test sessions against the same grid.
+    /// See `ElementPollerTicker::set_jitter()` for details. `jitter(0.0)` is a no-op.
+    pub fn jitter(mut self, fraction: f64) -> Self {
+        self.jitter = Some(fraction);
+        self
+    }
+
+    /// Skip the wait if the specified flag is true, e.g. for parts of the UI that are behind a
+    /// feature flag in some configurations:
+    ///
+    /// ```ignore
+    /// elem.wait_until().skip_if(!config.beta_banner).displayed().await?;
+    /// ```
+    ///
+    /// A skipped wait immediately returns `Ok(())` without making any WebDriver calls at all,
+    /// and is not reported to the `WaitObserver`. This applies to all wait methods, but not to
+    /// `check()` and `snapshot()`, which are not waits.
+    pub fn skip_if(mut self, skip: bool) -> Self {
+        self.skip = skip;
+        self
+    }
+
+    /// Call the specified function whenever the wait is about to poll again, with the number
+    /// of attempts so far and the elapsed time. This is a lightweight, per-wait alternative to
+    /// a `WaitObserver`, e.g. for a heartbeat during long waits in interactive runs:
+    ///
+    /// ```ignore
+    /// elem.wait_until()
+    ///     .on_poll(|attempt, elapsed| println!("Still waiting ({}, {:?})", attempt, elapsed))
+    ///     .displayed()
+    ///     .await?;
+    /// ```
+    ///
+    /// The function is not called after the final attempt, or if the first attempt succeeds.
+    /// It is called synchronously on the task running the wait, before sleeping until the
+    /// next attempt, so it should return quickly and must not block.
+    pub fn on_poll<F>(mut self, f: F) -> Self
+    where
+        F: Fn(usize, Duration) + Send + Sync + 'static,
+    {
+        self.on_poll = Some(PollCallback::new(f));
+        self
+    }
+
+    /// Fail the wait immediately if the specified predicate returns true, rather than waiting
+    /// for the timeout, e.g. to bail out as soon as a status shows an error while waiting for
+    /// it to show success:
+    ///
+    /// ```ignore
+    /// status
+    ///     .wait_until()
+    ///     .or_fail_if(conditions::element_has_class("error", true), "Saving failed")
+    ///     .has_text("Saved")
+    ///     .await?;
+    /// ```
+    ///
+    /// The predicate receives the waited element, but like any other predicate it is free to
+    /// check other state instead, e.g. an error banner elsewhere on the page via
+    /// `conditions::external_condition()`. It is checked on every poll before the wait's own
+    /// conditions, so if both are true at the same time, the wait fails. The wait then
+    /// returns `WebDriverError::CustomError` with the specified message, which is distinct from
+    /// the Timeout error returned if the conditions are never met. Any error returned by the
+    /// predicate is returned from the wait, so use the predicate's own error handling (e.g.
+    /// `ignore_errors = true` for the predicates in `conditions`) to ignore errors.
+    ///
+    /// This can be called multiple times to add several failure conditions, which are
+    /// checked in order. It applies to all wait methods of this waiter except
+    /// `no_descendants()`, which runs an `ElementQuery` instead.
+    pub fn or_fail_if(mut self, predicate: ElementPredicate, message: &str) -> Self {
+        self.fail_conditions.push(FailCondition {
+            predicate: Arc::new(predicate),
+            message: message.to_string(),
+        });
+        self
+    }
+
+    /// Read all state for `snapshot()` and `until_snapshot()` in a single script, so that
+    /// the states are consistent with each other even while the element is changing. This
+    /// also reads the element's attributes, and saves several WebDriver requests per poll.
+    ///
+    /// The snapshot contains whether the element is connected to the document, its
+    /// attributes, `innerText`, bounding rect (relative to the document), whether it is
+    /// enabled (i.e. does not match `:disabled`), whether it is checked or selected, and
+    /// whether it is displayed. Note that the script can only approximate WebDriver's
+    /// displayedness check: the element must have a non-empty rect and must not have
+    /// `display: none` or `visibility: hidden`.
+    ///
+    /// The built-in conditions of a chain started via `and()` are also evaluated against a
+    /// single snapshot per poll while this is enabled, so that e.g.
+    /// `and().displayed().and().has_text("Done").all()` cannot be satisfied by two different
+    /// states of the element. This applies to `displayed()`, `selected()`, `enabled()`,
+    /// `clickable()` (displayed and enabled), `has_class()`, `has_text()` and
+    /// `has_attribute()`, and their negations. Note that the text is `innerText` and the
+    /// attributes are the DOM attributes, which may differ slightly from what WebDriver reports.
+    /// If the chain contains any other condition, e.g. `has_value()` or a custom condition,
+    /// each condition reads the element state itself as usual.
+    ///
+    /// Predicates passed to `condition()` and `conditions()` cannot be evaluated against a
+    /// snapshot, because each of them reads the element state itself. To check arbitrary
+    /// states against the same point in time, combine them in a single `until_snapshot()`.
+    pub fn consistent_snapshot(mut self, consistent: bool) -> Self {
+        self.consistent_snapshot = consistent;
+        self
+    }
+
+    /// Scroll the element into view before each check of `clickable()`, so that the condition
+    /// reflects the state after scrolling. This avoids waiting on an element that WebDriver
+    /// considers clickable but which is off-screen. Disabled by default.
+    ///
+    /// NOTE: This changes the scroll position of the page on every poll iteration, which may
+    /// trigger scroll-based behaviour such as lazy loading. It also means that any other
+    /// viewport-based conditions checked afterwards (e.g. `not_obscured()`) see the element
+    /// scrolled into view.
+    pub fn scroll_into_view(mut self, enabled: bool) -> Self {
+        self.scroll_into_view = enabled;
+        self
+    }
+
+    /// Save a screenshot of the element to the specified directory each time a wait succeeds,
+    /// e.g. to keep an audit trail of the state that satisfied the condition(s). The file is
+    /// named after the error message set via `error()` (if any) and the current timestamp.
+    ///
+    /// Failing to save the screenshot does not affect the result of the wait. With the `log`
+    /// feature enabled, the failure is logged as a warning.
+    pub fn screenshot_on_success<P>(mut self, dir: P) -> Self
+    where
+        P: Into<PathBuf>,
+    {
+        self.success_screenshot_dir = Some(dir.into());
+        self
+    }
+
+    /// Record a timeout in the specified collector and return `Ok(())` instead of an error, so
+    /// that the test continues and can report all failures at the end via
+    /// `SoftAssertions::fail_if_any()`. The recorded message is the same as the message of the
+    /// Timeout error, so set a descriptive message via `error()`.
+    ///
+    /// NOTE: This changes control flow. A wait that returns `Ok(())` no longer means that the
+    /// condition(s) were met, so only use this deliberately, for checks whose failure does not
+    /// invalidate the rest of the test. Errors other than timeouts, such as those from
+    /// `or_fail_if()`, are still returned. `wait_for_value()` also still returns the Timeout
+    /// error, since it has no value to return otherwise.
+    pub fn soft(mut self, collector: &SoftAssertions) -> Self {
+        self.soft = Some(collector.clone());
+        self
+    }
+
+    /// Use the ElementPoller registered under the specified profile name for this ElementWaiter.
+    /// See `DefaultPollerConfig::set_poller_profile()`.
+    ///
+    /// If no profile with that name has been registered, the poller is left unchanged, i.e.
+    /// the default poller is used unless another poller was already set for this ElementWaiter.
+    pub fn use_profile(mut self, name: &str) -> Self {
+        if let Some(poller) = profile_poller(self.element.session, name) {
+            self.poller = poller;
+        }
+        self
+    }
+
+    /// Change only the interval of this ElementWaiter's poller, keeping its timeout.
+    /// See `ElementPoller::with_interval()` for how each variant is converted. Note that if the
+    /// poller is `NoWait`, this has no effect, since there is only a single attempt.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.poller = self.poller.with_interval(interval);
+        self
+    }
+
+    /// Change only the timeout of this ElementWaiter's poller, keeping its interval.
+    /// See `ElementPoller::with_timeout()` for how each variant is converted. If the poller is
+    /// `NoWait`, it becomes `TimeoutWithInterval` using the default interval of 500 milliseconds.
+    pub fn timeout_dur(mut self, timeout: Duration) -> Self {
+        self.poller = self.poller.with_timeout(timeout);
+        self
+    }
+
     /// Force this ElementWaiter to wait for the specified timeout, polling once
     /// after each interval. This will override the poller for this
     /// ElementWaiter only.
@@ -52,164 +480,1115 @@ impl<'a> ElementWaiter<'a> {
         self.with_poller(ElementPoller::TimeoutWithInterval(timeout, interval))
     }
 
-    async fn run_poller(&self, conditions: Vec<ElementPredicate>) -> WebDriverResult<bool> {
-        let mut ticker = ElementPollerTicker::new(self.poller.clone());
+    async fn run_poller(&self, conditions: Vec<ElementPredicate>) -> WebDriverResult<()> {
+        self.run_poller_until(conditions, None).await
+    }
+
+    /// Run the poller, stopping at the specified deadline (if any) instead of after the
+    /// poller's timeout. See `ElementPollerTicker::set_deadline()`.
+    async fn run_poller_until(
+        &self,
+        conditions: Vec<ElementPredicate>,
+        deadline: Option<Instant>,
+    ) -> WebDriverResult<()> {
+        let result = self.run_poller_strict(conditions, deadline).await;
+        self.soften(result)
+    }
+
+    /// If a SoftAssertions collector is set, record a Timeout error in it instead of returning
+    /// the error. See `soft()`.
+    fn soften(&self, result: WebDriverResult<()>) -> WebDriverResult<()> {
+        match (result, &self.soft) {
+            (Err(WebDriverError::Timeout(message)), Some(soft)) => {
+                soft.record(&message);
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+
+    /// Run the poller, returning any Timeout error even if a SoftAssertions collector is set.
+    async fn run_poller_strict(
+        &self,
+        conditions: Vec<ElementPredicate>,
+        deadline: Option<Instant>,
+    ) -> WebDriverResult<()> {
+        self.run_poller_checked(conditions, deadline).await?.map_err(WebDriverError::from)
+    }
+
+    /// Run the poller, returning a WaitError in the inner result if the poller timed out.
+    /// Any other error ends the wait early and is returned in the outer result.
+    async fn run_poller_checked(
+        &self,
+        conditions: Vec<ElementPredicate>,
+        deadline: Option<Instant>,
+    ) -> WebDriverResult<Result<(), WaitError>> {
+        self.run_poller_tracked(conditions, deadline, &mut None).await
+    }
+
+    /// Same as `run_poller_checked()`, but also store the element found again by
+    /// `refetch_on_stale()` (if any) in the specified slot, so that the caller can read the
+    /// state of the element that was checked last.
+    async fn run_poller_tracked(
+        &self,
+        conditions: Vec<ElementPredicate>,
+        deadline: Option<Instant>,
+        refetched: &mut Option<WebElement<'a>>,
+    ) -> WebDriverResult<Result<(), WaitError>> {
+        if self.skip {
+            return Ok(Ok(()));
+        }
+        let mut ticker = session_ticker(self.element.session, &self.poller)?;
+        if let Some(fraction) = self.jitter {
+            ticker.set_jitter(fraction, None);
+        }
+        if let Some(deadline) = deadline {
+            ticker.set_deadline(deadline);
+        }
+        ticker.set_on_poll(self.on_poll.clone());
+        let result = self.poll_conditions(&conditions, &mut ticker, refetched).await;
+        let description = if self.message.is_empty() {
+            "ElementWaiter"
+        } else {
+            &self.message
+        };
+        notify_observer(description, &ticker, matches!(result, Ok(Ok(()))));
+        result
+    }
+
+    async fn poll_conditions(
+        &self,
+        conditions: &[ElementPredicate],
+        ticker: &mut ElementPollerTicker,
+        refetched: &mut Option<WebElement<'a>>,
+    ) -> WebDriverResult<Result<(), WaitError>> {
+        let mut num_checks: u32 = 0;
+        let mut num_hits: usize = 0;
+        let mut last_error: Option<WebDriverError> = None;
         loop {
+            let element = refetched.as_ref().unwrap_or(&*self.element);
+            let check_start = Instant::now();
+            for fail in &self.fail_conditions {
+                if (fail.predicate)(element).await? {
+                    return Err(WebDriverError::CustomError(fail.message.clone()));
+                }
+            }
+
             let mut conditions_met = true;
-            for f in &conditions {
-                if !f(&self.element).await? {
-                    conditions_met = false;
-                    break;
+            let mut stale = false;
+            for f in conditions {
+                let result = match self.per_check_timeout {
+                    Some(t) => match check_with_timeout(f(element), t).await {
+                        Some(x) => x,
+                        None => {
+                            let e =
+                                WebDriverError::Timeout(format!("Check timed out after {:?}", t));
+                            if !self.ignore_errors {
+                                return Err(e);
+                            }
+                            conditions_met = false;
+                            last_error = Some(e);
+                            break;
+                        }
+                    },
+                    None => f(element).await,
+                };
+
+                match result {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        conditions_met = false;
+                        break;
+                    }
+                    Err(e @ WebDriverError::StaleElementReference(_))
+                        if self.refetch_by.is_some() =>
+                    {
+                        conditions_met = false;
+                        stale = true;
+                        last_error = Some(e);
+                        break;
+                    }
+                    Err(e) => return Err(e),
                 }
             }
 
             if conditions_met {
-                return Ok(true);
+                num_hits += 1;
+            }
+
+            if conditions_met && num_hits >= self.required_hits {
+                match self.min_wait.and_then(|x| x.checked_sub(ticker.elapsed())) {
+                    Some(remaining) if remaining > Duration::new(0, 0) => {
+                        sleep(remaining).await;
+                        continue;
+                    }
+                    _ => {
+                        self.save_success_screenshot(element).await;
+                        return Ok(Ok(()));
+                    }
+                }
+            }
+
+            ticker.record_check_duration(check_start.elapsed());
+            num_checks += 1;
+            if self.max_checks.is_some_and(|max| num_checks >= max) {
+                return Ok(Err(self.timeout(ticker, last_error)));
+            }
+
+            if let Some(by) = &self.refetch_by {
+                if stale || !element.is_present().await.unwrap_or(false) {
+                    // If the element can't be found (yet), try again on the next poll.
+                    if let Ok(x) = self.element.session.find_element(by.clone()).await {
+                        *refetched = Some(x);
+                    }
+                }
             }
 
             if !ticker.tick().await {
-                return Ok(false);
+                return Ok(Err(self.timeout(ticker, last_error)));
             }
         }
     }
 
-    fn timeout(self) -> WebDriverResult<()> {
-        Err(WebDriverError::Timeout(self.message))
+    /// Save a screenshot of the element if `screenshot_on_success()` is enabled.
+    async fn save_success_screenshot(&self, element: &WebElement<'_>) {
+        let dir = match &self.success_screenshot_dir {
+            Some(x) => x,
+            None => return,
+        };
+        let path = timestamped_path(dir, &self.message, "wait", "png");
+        let result = element.screenshot(&path).await;
+        #[cfg(feature = "log")]
+        {
+            if let Err(e) = result {
+                log::warn!("Failed to save screenshot to {}: {}", path.display(), e);
+            }
+        }
+        #[cfg(not(feature = "log"))]
+        {
+            let _ = result;
+        }
     }
 
-    pub async fn condition(self, f: ElementPredicate) -> WebDriverResult<()> {
-        match self.run_poller(vec![f]).await? {
-            true => Ok(()),
-            false => self.timeout(),
+    fn timeout(
+        &self,
+        ticker: &ElementPollerTicker,
+        last_error: Option<WebDriverError>,
+    ) -> WaitError {
+        let err = WaitError::new(&self.message, ticker.attempts(), ticker.elapsed());
+        match last_error {
+            Some(e) => err.with_source(e),
+            None => err,
         }
     }
 
-    pub async fn conditions(self, conditions: Vec<ElementPredicate>) -> WebDriverResult<()> {
-        match self.run_poller(conditions).await? {
-            true => Ok(()),
-            false => self.timeout(),
-        }
+    /// Read the current state of the element once, for debugging, e.g. to print what the
+    /// element looks like right now while developing a test:
+    ///
+    /// ```ignore
+    /// println!("{:?}", elem.wait_until().snapshot().await?);
+    /// ```
+    ///
+    /// This is a one-shot diagnostic, not a wait: the poller is ignored and each state is read
+    /// exactly once, so the states may not be consistent with each other if the element is
+    /// changing, unless `consistent_snapshot()` is used. Only an error checking whether the
+    /// element is present is returned.
+    pub async fn snapshot(&self) -> WebDriverResult<ElementStateSnapshot> {
+        conditions::element_state_snapshot(&self.element, self.consistent_snapshot).await
+    }
+
+    /// Wait until the specified function returns true for a snapshot of the element's state.
+    /// The snapshot is read once per poll, and all checks in the function see the same
+    /// snapshot:
+    ///
+    /// ```ignore
+    /// elem.wait_until()
+    ///     .consistent_snapshot(true)
+    ///     .until_snapshot(|s| s.displayed == Some(true) && s.text.as_deref() == Some("Done"))
+    ///     .await?;
+    /// ```
+    ///
+    /// See `consistent_snapshot()` for reading the snapshot in a single script, so that the
+    /// states are also consistent with each other.
+    pub async fn until_snapshot<F>(&self, f: F) -> WebDriverResult<()>
+    where
+        F: Fn(&ElementStateSnapshot) -> bool + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_snapshot_matches(
+            f,
+            self.consistent_snapshot,
+            ignore_errors,
+        ))
+        .await
     }
 
-    pub async fn stale(self) -> WebDriverResult<()> {
+    /// Check the specified predicate exactly once, ignoring the poller.
+    /// Returns whether the condition is currently met, rather than a timeout error.
+    pub async fn check(&self, f: ElementPredicate) -> WebDriverResult<bool> {
+        conditions::check_once(&self.element, &f).await
+    }
+
+    /// Wait until the specified predicate returns true. The predicate receives the element,
+    /// but it is free to ignore it and check other state instead.
+    pub async fn condition(&self, f: ElementPredicate) -> WebDriverResult<()> {
+        self.run_poller(vec![f]).await
+    }
+
+    /// Wait until the specified predicate returns true, stopping at the specified absolute
+    /// deadline instead of after the poller's timeout. This is useful for code that tracks
+    /// deadlines centrally, as it avoids recomputing the remaining duration for each wait.
+    ///
+    /// The poller's intervals still determine how often the predicate is checked, but its
+    /// timeout and minimum number of tries are ignored. The predicate is always checked at
+    /// least once, so if the deadline has already passed, this makes one immediate check and
+    /// returns a Timeout error if it fails. See `ElementPollerTicker::set_deadline()`.
+    pub async fn condition_until(
+        &self,
+        f: ElementPredicate,
+        deadline: Instant,
+    ) -> WebDriverResult<()> {
+        self.run_poller_until(vec![f], Some(deadline)).await
+    }
+
+    /// Wait until the specified async function returns true. This is the same as `condition()`,
+    /// but takes the function directly rather than an `ElementPredicate`, which saves boxing
+    /// and pinning the future. See `conditions::predicate()` for the kinds of functions that
+    /// can be used.
+    ///
+    /// Prefer `condition()` when reusing predicates from the `conditions` module, or when
+    /// the predicate needs to capture state.
+    pub async fn until<F>(&self, f: F) -> WebDriverResult<()>
+    where
+        F: for<'b> conditions::ElementCheck<'b> + Send + Sync + 'static,
+    {
+        self.condition(conditions::predicate(f)).await
+    }
+
+    /// Wait until the specified async function returns a value, and return it. This allows
+    /// gating on and extracting a value in one pass, e.g. parsing a price once it is valid:
+    ///
+    /// ```ignore
+    /// async fn price(elem: &WebElement<'_>) -> WebDriverResult<Option<f64>> {
+    ///     Ok(elem.text().await?.trim_start_matches('$').parse().ok())
+    /// }
+    ///
+    /// let price = elem.wait_until().wait_for_value(price).await?;
+    /// ```
+    ///
+    /// `Ok(Some(value))` means the wait is complete, and `Ok(None)` means the value is not
+    /// available yet, so it will be checked again. An error is returned immediately, unless
+    /// errors are ignored for this waiter (see `ignore_errors()`), in which case it is treated
+    /// the same as `Ok(None)`. If `require_hits()` is used, the value from the last check is
+    /// returned.
+    ///
+    /// There is no value to return if the wait is skipped, so with `skip_if(true)` the
+    /// function is called once without waiting, and `Ok(None)` results in a Timeout error.
+    pub async fn wait_for_value<T, F>(&self, f: F) -> WebDriverResult<T>
+    where
+        T: Send + 'static,
+        F: for<'b> conditions::ElementValueCheck<'b, T> + Send + Sync + 'static,
+    {
+        if self.skip {
+            return f(&self.element).await?.ok_or_else(|| {
+                WebDriverError::Timeout(String::from("Wait skipped and no value was available"))
+            });
+        }
+
+        let value: Arc<Mutex<Option<T>>> = Arc::new(Mutex::new(None));
+        let slot = value.clone();
         let ignore_errors = self.ignore_errors;
+        let f: ElementPredicate = Box::new(move |elem| {
+            let slot = slot.clone();
+            let result = f(elem);
+            Box::pin(async move {
+                match result.await {
+                    Ok(Some(x)) => {
+                        *slot.lock().unwrap() = Some(x);
+                        Ok(true)
+                    }
+                    Ok(None) => Ok(false),
+                    Err(e) => handle_errors(Err(e), ignore_errors),
+                }
+            })
+        });
+        self.run_poller_strict(vec![f], None).await?;
+
+        let value = value.lock().unwrap().take();
+        Ok(value.expect("value is stored before the condition returns true"))
+    }
+
+    /// Wait until either the success or the failure predicate returns true, and return which
+    /// one did, or `WaitOutcome::TimedOut` if neither did before the poller timed out. This is
+    /// useful for branching on the result rather than treating everything but success as an
+    /// error:
+    ///
+    /// ```ignore
+    /// match status
+    ///     .wait_until()
+    ///     .outcome(
+    ///         conditions::element_has_text("Saved", true),
+    ///         conditions::element_has_class("error", true),
+    ///     )
+    ///     .await?
+    /// {
+    ///     WaitOutcome::Success => {}
+    ///     WaitOutcome::Failure => retry_save().await?,
+    ///     WaitOutcome::TimedOut => panic!("status never updated"),
+    /// }
+    /// ```
+    ///
+    /// On each poll, the failure predicate is checked first, and the success predicate is
+    /// only checked if it returned false, so if both are true at the same time, the outcome
+    /// is `Failure`. This matches `or_fail_if()`. Errors returned by the predicates (depending
+    /// on their own error handling) and by any `or_fail_if()` conditions are returned as
+    /// errors, as is a `Timeout` error from `per_check_timeout()` if errors are not ignored.
+    /// Only the poller timing out results in `TimedOut`, and it is never recorded by `soft()`.
+    /// A skipped wait (see `skip_if()`) returns `Success`, like other skipped waits.
+    pub async fn outcome(
+        &self,
+        success: ElementPredicate,
+        failure: ElementPredicate,
+    ) -> WebDriverResult<WaitOutcome> {
+        let outcome: Arc<Mutex<Option<WaitOutcome>>> = Arc::new(Mutex::new(None));
+        let slot = outcome.clone();
+        let f: ElementPredicate = Box::new(move |elem| {
+            let slot = slot.clone();
+            let failure = failure(elem);
+            let success = success(elem);
+            Box::pin(async move {
+                let result = if failure.await? {
+                    WaitOutcome::Failure
+                } else if success.await? {
+                    WaitOutcome::Success
+                } else {
+                    return Ok(false);
+                };
+                *slot.lock().unwrap() = Some(result);
+                Ok(true)
+            })
+        });
+
+        match self.run_poller_checked(vec![f], None).await? {
+            Ok(()) => {
+                let outcome = outcome.lock().unwrap().take();
+                Ok(outcome.unwrap_or(WaitOutcome::Success))
+            }
+            Err(_) => Ok(WaitOutcome::TimedOut),
+        }
+    }
+
+    /// Wait for a transition: first until `from` returns true, and then until `to` returns
+    /// true. This observes a sequence of states that a single wait for the end state would
+    /// miss, e.g. a submit button that is disabled while submitting and then enabled again:
+    ///
+    /// ```ignore
+    /// button.click().await?;
+    /// button
+    ///     .wait_until()
+    ///     .observe_transition(
+    ///         conditions::element_is_not_enabled(true),
+    ///         conditions::element_is_enabled(true),
+    ///     )
+    ///     .await?;
+    /// ```
+    ///
+    /// The two phases run in a single polling loop, so they share the poller's budget: the
+    /// timeout (or number of tries) covers both phases together rather than each of them.
+    /// Each poll checks only the predicate of the current phase. Once `from` returns true, the
+    /// wait switches to `to`, which is first checked on the next poll, so `to` must hold after
+    /// `from` has been observed, even if it was already true at the same time. `from` is not
+    /// checked again after that.
+    ///
+    /// NOTE: A state that lasts less than one poll interval may be missed entirely, so use a
+    /// short interval for brief intermediate states. On timeout, the error message states
+    /// which phase the wait was in.
+    pub async fn observe_transition(
+        &self,
+        from: ElementPredicate,
+        to: ElementPredicate,
+    ) -> WebDriverResult<()> {
+        let observed_from = Arc::new(Mutex::new(false));
+        let phase = observed_from.clone();
+        let f: ElementPredicate = Box::new(move |elem| {
+            let phase = phase.clone();
+            let in_from_phase = !*phase.lock().unwrap();
+            let check = if in_from_phase {
+                from(elem)
+            } else {
+                to(elem)
+            };
+            Box::pin(async move {
+                let result = check.await?;
+                if in_from_phase {
+                    if result {
+                        *phase.lock().unwrap() = true;
+                    }
+                    // The target state is only checked from the next poll onwards.
+                    Ok(false)
+                } else {
+                    Ok(result)
+                }
+            })
+        });
+
+        let result = match self.run_poller_strict(vec![f], None).await {
+            Err(WebDriverError::Timeout(message)) => {
+                let phase = if *observed_from.lock().unwrap() {
+                    "the initial state was observed, but not the target state"
+                } else {
+                    "the initial state was never observed"
+                };
+                Err(WebDriverError::Timeout(format!("{} ({})", message, phase)))
+            }
+            result => result,
+        };
+        self.soften(result)
+    }
+
+    /// Wait until the specified synchronous function returns true. This is useful for cheap
+    /// checks that do not need to make any WebDriver requests, e.g. checking state captured
+    /// elsewhere. The element is passed in for identity only; calling async methods on it is
+    /// not possible here, so use `until()` or `condition()` for checks that need to.
+    pub async fn until_sync<F>(&self, f: F) -> WebDriverResult<()>
+    where
+        F: Fn(&WebElement) -> bool + Send + Sync + 'static,
+    {
         self.condition(Box::new(move |elem| {
-            Box::pin(
-                async move { handle_errors(elem.is_present().await.map(|x| !x), ignore_errors) },
-            )
+            let result = f(elem);
+            Box::pin(async move { Ok(result) })
         }))
         .await
     }
 
-    pub async fn displayed(self) -> WebDriverResult<()> {
+    pub async fn conditions(&self, conditions: Vec<ElementPredicate>) -> WebDriverResult<()> {
+        self.run_poller(conditions).await
+    }
+
+    /// Wait until the specified predicate returns true, like `condition()`, but return the
+    /// structured `WaitError` if the poller times out, rather than converting it into
+    /// `WebDriverError::Timeout`. See `try_conditions()`.
+    pub async fn try_condition(
+        &self,
+        f: ElementPredicate,
+    ) -> WebDriverResult<Result<(), WaitError>> {
+        self.try_conditions(vec![f]).await
+    }
+
+    /// Wait until all of the specified predicates return true, like `conditions()`, but return
+    /// the structured `WaitError` if the poller times out. The WaitError keeps the number of
+    /// attempts, the elapsed time and the last error recorded while polling as its `source()`,
+    /// so converting it into `anyhow` or `color_eyre` errors preserves the whole chain:
+    ///
+    /// ```ignore
+    /// elem.wait_until().try_condition(conditions::element_is_displayed(true)).await??;
+    /// ```
+    ///
+    /// Errors that end the wait early, e.g. from a predicate or an `or_fail_if()` condition,
+    /// are returned in the outer result. A timeout is never recorded by `soft()`.
+    pub async fn try_conditions(
+        &self,
+        conditions: Vec<ElementPredicate>,
+    ) -> WebDriverResult<Result<(), WaitError>> {
+        self.run_poller_checked(conditions, None).await
+    }
+
+    /// Wait until the specified async function returns true, using the same polling settings
+    /// as for element conditions. This is useful for waiting on external state, such as a
+    /// backend record being created. See `conditions::external_condition()`.
+    pub async fn external_condition<F, Fut>(&self, f: F) -> WebDriverResult<()>
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = WebDriverResult<bool>> + Send + 'static,
+    {
+        self.condition(conditions::external_condition(f)).await
+    }
+
+    /// Start a chain of conditions that will all be checked together in a single poll loop,
+    /// rather than waiting for each condition in turn. The conditions are only run once
+    /// `all()` is awaited.
+    ///
+    /// ```ignore
+    /// elem.wait_until().and().displayed().and().enabled().all().await?;
+    /// ```
+    pub fn and(&self) -> ElementWaiterChain<'a> {
+        ElementWaiterChain {
+            waiter: self.clone(),
+            conditions: Vec::new(),
+            snapshot_checks: Some(Vec::new()),
+        }
+    }
+
+    pub async fn stale(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_stale(ignore_errors)).await
+    }
+
+    /// Best-effort version of `stale()`, for non-critical teardown steps such as waiting for a
+    /// toast to disappear before the next test starts.
+    ///
+    /// This waits for the element to go stale as usual, but never fails: if the poller times
+    /// out, or any other error occurs, the error is discarded and `Ok(())` is returned. With
+    /// the `log` feature enabled, the error is logged as a warning. Do not use this where the
+    /// element disappearing matters for the correctness of the test.
+    pub async fn stale_or_ignore(&self) -> WebDriverResult<()> {
+        let result = self.stale().await;
+        #[cfg(feature = "log")]
+        {
+            if let Err(e) = result {
+                log::warn!("Ignoring failed wait for element to go stale: {}", e);
+            }
+        }
+        #[cfg(not(feature = "log"))]
+        {
+            let _ = result;
+        }
+        Ok(())
+    }
+
+    /// Wait until no descendant of the element matches the specified selector, e.g. until a
+    /// specific child has been removed while the element itself persists. This does not
+    /// require holding a reference to the child, unlike `stale()`.
+    ///
+    /// The selector is run relative to the element, and XPath selectors starting with `//`
+    /// are rewritten to be relative (see `ElementQuery::relative_xpath()`). This uses the
+    /// poller and error handling of this waiter, and on timeout returns the error from
+    /// `ElementQuery::none()`, which lists the selector that still matched. If errors are
+    /// ignored, a poll iteration in which the selector fails with an error counts as the
+    /// descendant still being present, rather than as it being gone.
+    pub async fn no_descendants(&self, by: By<'_>) -> WebDriverResult<()> {
+        if self.skip {
+            return Ok(());
+        }
+        let result = self
+            .element
+            .query(by)
+            .relative_xpath(true)
+            .ignore_errors(self.ignore_errors)
+            .retry_on_error(self.ignore_errors)
+            .with_poller(self.poller.clone())
+            .none()
+            .await;
+        self.soften(result)
+    }
+
+    pub async fn invalidated(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_invalid(ignore_errors)).await
+    }
+
+    pub async fn dismissed_or_changed<S>(&self, baseline_text: S) -> WebDriverResult<()>
+    where
+        S: Into<String>,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_gone_or_text_changed(baseline_text, ignore_errors)).await
+    }
+
+    pub async fn displayed(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_displayed(ignore_errors)).await
     }
 
-    pub async fn not_displayed(self) -> WebDriverResult<()> {
+    pub async fn not_displayed(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_not_displayed(ignore_errors)).await
     }
 
-    pub async fn selected(self) -> WebDriverResult<()> {
+    pub async fn selected(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_selected(ignore_errors)).await
     }
 
-    pub async fn not_selected(self) -> WebDriverResult<()> {
+    pub async fn not_selected(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_not_selected(ignore_errors)).await
     }
 
-    pub async fn enabled(self) -> WebDriverResult<()> {
+    pub async fn enabled(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_enabled(ignore_errors)).await
     }
 
-    pub async fn not_enabled(self) -> WebDriverResult<()> {
+    pub async fn not_enabled(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_not_enabled(ignore_errors)).await
     }
 
-    pub async fn clickable(self) -> WebDriverResult<()> {
+    pub async fn checked(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_property_checked(ignore_errors)).await
+    }
+
+    pub async fn not_checked(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_property_not_checked(ignore_errors)).await
+    }
+
+    pub async fn property_disabled(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_property_disabled(ignore_errors)).await
+    }
+
+    pub async fn aria_disabled(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_aria_disabled(ignore_errors)).await
+    }
+
+    pub async fn clickable(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_is_clickable(ignore_errors)).await
+        if self.scroll_into_view {
+            let scroll: ElementPredicate = Box::new(move |elem| {
+                Box::pin(async move {
+                    handle_errors(elem.scroll_into_view().await.map(|_| true), ignore_errors)
+                })
+            });
+            self.conditions(vec![scroll, conditions::element_is_clickable(ignore_errors)]).await
+        } else {
+            self.condition(conditions::element_is_clickable(ignore_errors)).await
+        }
     }
 
-    pub async fn not_clickable(self) -> WebDriverResult<()> {
+    pub async fn not_clickable(&self) -> WebDriverResult<()> {
         let ignore_errors = self.ignore_errors;
         self.condition(conditions::element_is_not_clickable(ignore_errors)).await
     }
 
-    pub async fn has_class<N>(self, class_name: N) -> WebDriverResult<()>
+    pub async fn laid_out(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_nonzero_size(ignore_errors)).await
+    }
+
+    pub async fn dom_quiet(&self, quiet: Duration) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_dom_quiet(quiet, ignore_errors)).await
+    }
+
+    pub async fn has_offset_parent(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_offset_parent(ignore_errors)).await
+    }
+
+    pub async fn not_obscured(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_not_obscured(ignore_errors)).await
+    }
+
+    pub async fn topmost(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_topmost(ignore_errors)).await
+    }
+
+    /// Wait until the element is ready to be interacted with, i.e. it is present, displayed,
+    /// enabled, not moving and not obscured. See `conditions::element_is_ready()`.
+    pub async fn ready(&self) -> WebDriverResult<()> {
+        self.ready_with(ReadyOptions::default()).await
+    }
+
+    /// Wait until the element is present and passes the checks enabled in the specified
+    /// options. See `conditions::element_is_ready_with()`.
+    pub async fn ready_with(&self, options: ReadyOptions) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_ready_with(options, ignore_errors)).await
+    }
+
+    /// Wait until the element (typically a submit button) is enabled and neither it nor its
+    /// form is marked as busy, i.e. a previous submission has finished. This is a heuristic,
+    /// see `conditions::element_not_submitting()`.
+    ///
+    /// The busy marker is the CSS selector `[aria-busy="true"]` by default, and can be changed
+    /// for all waiters created from a WebDriver via the "BusySelector" session config key, e.g.
+    /// `driver.config_mut().set("BusySelector", ".is-submitting")?`. Use
+    /// `ready_to_submit_with()` to specify the selector for a single wait.
+    pub async fn ready_to_submit(&self) -> WebDriverResult<()> {
+        let busy_selector: String = self
+            .element
+            .session
+            .config()
+            .get("BusySelector")
+            .unwrap_or_else(|_| String::from(conditions::DEFAULT_BUSY_SELECTOR));
+        self.ready_to_submit_with(&busy_selector).await
+    }
+
+    /// Wait until the element is enabled and neither it nor its form matches the specified
+    /// busy selector. See `ready_to_submit()`.
+    pub async fn ready_to_submit_with(&self, busy_selector: &str) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_not_submitting_with(busy_selector, ignore_errors)).await
+    }
+
+    /// Wait until the element has finished loading, i.e. it no longer has the loading class
+    /// and is displayed. This is the common pattern of a spinner or skeleton class that is
+    /// removed once the content is ready.
+    ///
+    /// The loading class is `loading` by default, and can be changed for all waiters created
+    /// from a WebDriver via the "LoadingClass" session config key, e.g.
+    /// `driver.config_mut().set("LoadingClass", "is-loading")?`. Use `finished_loading_with()`
+    /// to specify the class for a single wait. The class is matched as a whole word, so
+    /// `loading` does not match `loading-overlay`.
+    pub async fn finished_loading(&self) -> WebDriverResult<()> {
+        let class_name: String = self
+            .element
+            .session
+            .config()
+            .get("LoadingClass")
+            .unwrap_or_else(|_| String::from("loading"));
+        self.finished_loading_with(&class_name).await
+    }
+
+    /// Wait until the element no longer has the specified loading class and is displayed.
+    /// See `finished_loading()`.
+    pub async fn finished_loading_with(&self, class_name: &str) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.conditions(vec![
+            conditions::element_lacks_class(StringMatch::new(class_name).word(), ignore_errors),
+            conditions::element_is_displayed(ignore_errors),
+        ])
+        .await
+    }
+
+    pub async fn contenteditable(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_contenteditable(ignore_errors)).await
+    }
+
+    pub async fn not_contenteditable(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_not_contenteditable(ignore_errors)).await
+    }
+
+    pub async fn focus_within(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_focus_within(ignore_errors)).await
+    }
+
+    pub async fn tab_selected(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_selected_tab(ignore_errors)).await
+    }
+
+    pub async fn tab_selected_with_panel(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_selected_tab_with_panel(ignore_errors)).await
+    }
+
+    pub async fn has_class<N>(&self, class_name: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_class(class_name, ignore_errors)).await
+    }
+
+    pub async fn classes_equal(&self, class_names: &[&str]) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_classes_equal(class_names, ignore_errors)).await
+    }
+
+    pub async fn lacks_class<N>(&self, class_name: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_class(class_name, ignore_errors)).await
+    }
+
+    pub async fn has_text<N>(&self, text: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_text(text, ignore_errors)).await
+    }
+
+    /// Wait until the element's text is exactly equal to the specified text. The comparison is
+    /// case-sensitive and uses the rendered text as returned by `WebElement::text()`, which
+    /// typically has leading and trailing whitespace removed by the browser.
+    ///
+    /// Use `text_contains()` to match part of the text, or `has_text()` for other matching
+    /// (e.g. a `Regex` or a case-insensitive `StringMatch`).
+    pub async fn text_equals(&self, text: &str) -> WebDriverResult<()> {
+        self.has_text(StringMatch::new(text)).await
+    }
+
+    /// Wait until the element's text contains the specified text anywhere within it. The
+    /// comparison is case-sensitive. See `text_equals()` for an exact match.
+    pub async fn text_contains(&self, text: &str) -> WebDriverResult<()> {
+        self.has_text(StringMatch::new(text).partial()).await
+    }
+
+    pub async fn normalized_text_is<N>(&self, text: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_normalized_text_is(text, ignore_errors)).await
+    }
+
+    pub async fn inner_html_matches<N>(&self, needle: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_inner_html_matches(needle, ignore_errors)).await
+    }
+
+    pub async fn lacks_text<N>(&self, text: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_text(text, ignore_errors)).await
+    }
+
+    pub async fn text_parses<F, T>(&self, parser: F) -> WebDriverResult<()>
+    where
+        F: Fn(&str) -> Option<T> + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_parses(parser, ignore_errors)).await
+    }
+
+    pub async fn timestamp_within<F>(&self, window: Duration, parser: F) -> WebDriverResult<()>
+    where
+        F: Fn(&str) -> Option<SystemTime> + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_timestamp_within(window, parser, ignore_errors))
+            .await
+    }
+
+    pub async fn text_is_valid_json(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_is_valid_json(ignore_errors)).await
+    }
+
+    pub async fn text_is_valid_rfc3339_date(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_is_valid_rfc3339_date(ignore_errors)).await
+    }
+
+    /// Wait until the element's text is one of the specified set.
+    /// See `conditions::element_text_in()`. On timeout, the element's last observed text and
+    /// the allowed set are included in the error. With `refetch_on_stale()`, the text is read
+    /// from the element that was found again, if any.
+    pub async fn text_in<N>(&self, texts: &[N]) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + fmt::Debug + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        let mut refetched = None;
+        let conditions = vec![conditions::element_text_in(texts, ignore_errors)];
+        let result = self
+            .run_poller_tracked(conditions, None, &mut refetched)
+            .await
+            .and_then(|x| x.map_err(WebDriverError::from));
+        let result = match result {
+            Err(WebDriverError::Timeout(message)) => {
+                // Read the text of the element being polled, which may have been found again.
+                let element = refetched.as_ref().unwrap_or(&*self.element);
+                let observed = match element.text().await {
+                    Ok(x) => format!("{:?}", x),
+                    Err(e) => format!("unavailable ({})", e),
+                };
+                Err(WebDriverError::Timeout(format!(
+                    "{} (text: {}, expected one of: {:?})",
+                    message, observed, texts
+                )))
+            }
+            result => result,
+        };
+        self.soften(result)
+    }
+
+    pub async fn text_contains_any<N>(&self, texts: &[N]) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_contains_any(texts, ignore_errors)).await
+    }
+
+    pub async fn text_contains_all<N>(&self, texts: &[N]) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_contains_all(texts, ignore_errors)).await
+    }
+
+    pub async fn has_own_text<N>(&self, text: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_own_text(text, ignore_errors)).await
+    }
+
+    pub async fn text_len_between(&self, min: usize, max: usize) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_len_between(min, max, ignore_errors)).await
+    }
+
+    pub async fn has_value<N>(&self, value: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_value(value, ignore_errors)).await
+    }
+
+    pub async fn number_between(&self, min: f64, max: f64) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_number_value_between(min, max, ignore_errors)).await
+    }
+
+    /// Wait until the element's text is a number greater than or equal to `target`.
+    ///
+    /// If `monotonic` is true, this fails immediately if the number ever decreases while
+    /// waiting, rather than continuing to wait. See `conditions::element_number_reaches()`
+    /// for details on how the text is parsed.
+    pub async fn number_reaches(&self, target: f64, monotonic: bool) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        if monotonic {
+            self.condition(conditions::element_number_reaches_monotonic(target, ignore_errors))
+                .await
+        } else {
+            self.condition(conditions::element_number_reaches(target, ignore_errors)).await
+        }
+    }
+
+    pub async fn attr_number_reaches(&self, name: &str, target: f64) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_attr_number_reaches(name, target, ignore_errors)).await
+    }
+
+    pub async fn attr_number_at_least(&self, name: &str, min: f64) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_attr_number_at_least(name, min, ignore_errors)).await
+    }
+
+    pub async fn dirty(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_dirty(ignore_errors)).await
+    }
+
+    pub async fn pristine(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_pristine(ignore_errors)).await
+    }
+
+    pub async fn lacks_value<N>(&self, value: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_lacks_value(value, ignore_errors)).await
+    }
+
+    pub async fn has_attribute<S, N>(&self, attribute_name: S, value: N) -> WebDriverResult<()>
     where
+        S: Into<String>,
         N: Needle + Clone + Send + Sync + 'static,
     {
         let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_has_class(class_name, ignore_errors)).await
+        self.condition(conditions::element_has_attribute(attribute_name, value, ignore_errors))
+            .await
     }
 
-    pub async fn lacks_class<N>(self, class_name: N) -> WebDriverResult<()>
+    /// Wait until the element's `id` attribute matches the specified needle, e.g. for ids that
+    /// are generated by a framework. This is a shorthand for `has_attribute("id", needle)`,
+    /// so it checks the attribute, not the `id` property.
+    pub async fn id_matches<N>(&self, needle: N) -> WebDriverResult<()>
     where
         N: Needle + Clone + Send + Sync + 'static,
     {
-        let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_lacks_class(class_name, ignore_errors)).await
+        self.has_attribute("id", needle).await
     }
 
-    pub async fn has_text<N>(self, text: N) -> WebDriverResult<()>
+    /// Wait until the element's `name` attribute matches the specified needle. This is a
+    /// shorthand for `has_attribute("name", needle)`, so it checks the attribute, not the
+    /// `name` property.
+    pub async fn name_matches<N>(&self, needle: N) -> WebDriverResult<()>
     where
         N: Needle + Clone + Send + Sync + 'static,
     {
-        let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_has_text(text, ignore_errors)).await
+        self.has_attribute("name", needle).await
     }
 
-    pub async fn lacks_text<N>(self, text: N) -> WebDriverResult<()>
+    pub async fn style_contains<N>(&self, value: N) -> WebDriverResult<()>
     where
         N: Needle + Clone + Send + Sync + 'static,
     {
         let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_lacks_text(text, ignore_errors)).await
+        self.condition(conditions::element_style_contains(value, ignore_errors)).await
+    }
+
+    pub async fn data_attr_is(&self, name: &str, value: &str) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_data_attr_is(name, value, ignore_errors)).await
     }
 
-    pub async fn has_value<N>(self, value: N) -> WebDriverResult<()>
+    /// Wait until the element's `data-state` attribute is exactly equal to the specified value.
+    /// This is shorthand for `data_attr_is("state", value)`.
+    pub async fn data_state_is(&self, value: &str) -> WebDriverResult<()> {
+        self.data_attr_is("state", value).await
+    }
+
+    /// Wait until the element has a `title` attribute (i.e. a native tooltip) matching the
+    /// specified value. This is shorthand for `has_attribute("title", text)`.
+    pub async fn has_tooltip<N>(&self, text: N) -> WebDriverResult<()>
     where
         N: Needle + Clone + Send + Sync + 'static,
     {
-        let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_has_value(value, ignore_errors)).await
+        self.has_attribute("title", text).await
     }
 
-    pub async fn lacks_value<N>(self, value: N) -> WebDriverResult<()>
+    /// Like `has_tooltip()`, but moves the mouse over the center of the element before each
+    /// check, for UIs that only set the `title` attribute when the element is hovered.
+    ///
+    /// NOTE: This uses the WebDriver Actions API to move the mouse, which leaves the mouse
+    /// over the element afterwards and may trigger other hover effects, such as opening
+    /// menus. The element must be in view for the mouse to be moved over it.
+    pub async fn hover_has_tooltip<N>(&self, text: N) -> WebDriverResult<()>
     where
         N: Needle + Clone + Send + Sync + 'static,
     {
         let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_lacks_value(value, ignore_errors)).await
+        let hover: ElementPredicate = Box::new(move |elem| {
+            Box::pin(async move {
+                let result = elem.session.action_chain().move_to_element_center(elem).perform();
+                handle_errors(result.await.map(|_| true), ignore_errors)
+            })
+        });
+        self.conditions(vec![
+            hover,
+            conditions::element_has_attribute("title", text, ignore_errors),
+        ])
+        .await
     }
 
-    pub async fn has_attribute<S, N>(self, attribute_name: S, value: N) -> WebDriverResult<()>
+    pub async fn lacks_attribute<S, N>(&self, attribute_name: S, value: N) -> WebDriverResult<()>
     where
         S: Into<String>,
         N: Needle + Clone + Send + Sync + 'static,
     {
         let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_has_attribute(attribute_name, value, ignore_errors))
+        self.condition(conditions::element_lacks_attribute(attribute_name, value, ignore_errors))
             .await
     }
 
-    pub async fn lacks_attribute<S, N>(self, attribute_name: S, value: N) -> WebDriverResult<()>
+    pub async fn attribute_satisfies<S, F>(&self, attribute_name: S, f: F) -> WebDriverResult<()>
     where
         S: Into<String>,
-        N: Needle + Clone + Send + Sync + 'static,
+        F: Fn(Option<&str>) -> bool + Send + Sync + 'static,
     {
         let ignore_errors = self.ignore_errors;
-        self.condition(conditions::element_lacks_attribute(attribute_name, value, ignore_errors))
+        self.condition(conditions::element_attribute_satisfies(attribute_name, f, ignore_errors))
             .await
     }
 
-    pub async fn has_attributes<S, N>(self, desired_attributes: &[(S, N)]) -> WebDriverResult<()>
+    pub async fn has_attributes<S, N>(&self, desired_attributes: &[(S, N)]) -> WebDriverResult<()>
     where
         S: Into<String> + Clone,
         N: Needle + Clone + Send + Sync + 'static,
@@ -218,7 +1597,7 @@ impl<'a> ElementWaiter<'a> {
         self.condition(conditions::element_has_attributes(desired_attributes, ignore_errors)).await
     }
 
-    pub async fn lacks_attributes<S, N>(self, desired_attributes: &[(S, N)]) -> WebDriverResult<()>
+    pub async fn lacks_attributes<S, N>(&self, desired_attributes: &[(S, N)]) -> WebDriverResult<()>
     where
         S: Into<String> + Clone,
         N: Needle + Clone + Send + Sync + 'static,
@@ -228,7 +1607,7 @@ impl<'a> ElementWaiter<'a> {
             .await
     }
 
-    pub async fn has_property<S, N>(self, property_name: S, value: N) -> WebDriverResult<()>
+    pub async fn has_property<S, N>(&self, property_name: S, value: N) -> WebDriverResult<()>
     where
         S: Into<String>,
         N: Needle + Clone + Send + Sync + 'static,
@@ -237,7 +1616,7 @@ impl<'a> ElementWaiter<'a> {
         self.condition(conditions::element_has_property(property_name, value, ignore_errors)).await
     }
 
-    pub async fn lacks_property<S, N>(self, property_name: S, value: N) -> WebDriverResult<()>
+    pub async fn lacks_property<S, N>(&self, property_name: S, value: N) -> WebDriverResult<()>
     where
         S: Into<String>,
         N: Needle + Clone + Send + Sync + 'static,
@@ -247,7 +1626,7 @@ impl<'a> ElementWaiter<'a> {
             .await
     }
 
-    pub async fn has_properties<S, N>(self, desired_properties: &[(S, N)]) -> WebDriverResult<()>
+    pub async fn has_properties<S, N>(&self, desired_properties: &[(S, N)]) -> WebDriverResult<()>
     where
         S: Into<String> + Clone,
         N: Needle + Clone + Send + Sync + 'static,
@@ -256,7 +1635,7 @@ impl<'a> ElementWaiter<'a> {
         self.condition(conditions::element_has_properties(desired_properties, ignore_errors)).await
     }
 
-    pub async fn lacks_properties<S, N>(self, desired_properties: &[(S, N)]) -> WebDriverResult<()>
+    pub async fn lacks_properties<S, N>(&self, desired_properties: &[(S, N)]) -> WebDriverResult<()>
     where
         S: Into<String> + Clone,
         N: Needle + Clone + Send + Sync + 'static,
@@ -266,7 +1645,11 @@ impl<'a> ElementWaiter<'a> {
             .await
     }
 
-    pub async fn has_css_property<S, N>(self, css_property_name: S, value: N) -> WebDriverResult<()>
+    pub async fn has_css_property<S, N>(
+        &self,
+        css_property_name: S,
+        value: N,
+    ) -> WebDriverResult<()>
     where
         S: Into<String>,
         N: Needle + Clone + Send + Sync + 'static,
@@ -281,7 +1664,7 @@ impl<'a> ElementWaiter<'a> {
     }
 
     pub async fn lacks_css_property<S, N>(
-        self,
+        &self,
         css_property_name: S,
         value: N,
     ) -> WebDriverResult<()>
@@ -299,7 +1682,7 @@ impl<'a> ElementWaiter<'a> {
     }
 
     pub async fn has_css_properties<S, N>(
-        self,
+        &self,
         desired_css_properties: &[(S, N)],
     ) -> WebDriverResult<()>
     where
@@ -315,7 +1698,7 @@ impl<'a> ElementWaiter<'a> {
     }
 
     pub async fn lacks_css_properties<S, N>(
-        self,
+        &self,
         desired_css_properties: &[(S, N)],
     ) -> WebDriverResult<()>
     where
@@ -329,6 +1712,475 @@ impl<'a> ElementWaiter<'a> {
         ))
         .await
     }
+
+    /// Wait until the computed `position` of the element matches the specified needle,
+    /// e.g. `position_is("sticky")`. See `conditions::element_position_is()`.
+    pub async fn position_is<N>(&self, position: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_position_is(position, ignore_errors)).await
+    }
+
+    /// Wait until the computed `direction` of the element is the specified direction, e.g.
+    /// `direction_is("rtl")`. See `conditions::element_direction_is()`.
+    pub async fn direction_is(&self, direction: &str) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_direction_is(direction, ignore_errors)).await
+    }
+
+    /// Wait until the element is the nth child of its parent. Note that `n` is 0-based.
+    /// See `conditions::element_is_nth_child()`.
+    pub async fn nth_child(&self, n: usize) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_nth_child(n, ignore_errors)).await
+    }
+
+    pub async fn overflowing(&self, axis: OverflowAxis) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_overflowing(axis, ignore_errors)).await
+    }
+
+    pub async fn not_overflowing(&self, axis: OverflowAxis) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_not_overflowing(axis, ignore_errors)).await
+    }
+
+    /// Wait until the element satisfies its HTML5 constraint validation. On timeout, the
+    /// element's `validationMessage` is included in the error.
+    pub async fn form_valid(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        let result =
+            self.run_poller_strict(vec![conditions::element_is_valid(ignore_errors)], None).await;
+        let result = match result {
+            Err(WebDriverError::Timeout(message)) => {
+                let validation_message = self
+                    .element
+                    .get_property("validationMessage")
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default();
+                Err(WebDriverError::Timeout(format!(
+                    "{} (validation message: {})",
+                    message, validation_message
+                )))
+            }
+            result => result,
+        };
+        self.soften(result)
+    }
+
+    pub async fn form_invalid(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_invalid_form_field(ignore_errors)).await
+    }
+
+    pub async fn visibility_is<N>(&self, visibility: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_css_visibility_is(visibility, ignore_errors)).await
+    }
+
+    pub async fn cursor_is<N>(&self, cursor: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_cursor_is(cursor, ignore_errors)).await
+    }
+
+    pub async fn draggable(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_draggable(ignore_errors)).await
+    }
+
+    pub async fn opacity_is(&self, opacity: f64, tolerance: f64) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_opacity_is(opacity, tolerance, ignore_errors)).await
+    }
+
+    pub async fn color_is(
+        &self,
+        property: &str,
+        expected_rgba: (u8, u8, u8, u8),
+        tolerance: u8,
+    ) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_color_is(
+            property,
+            expected_rgba,
+            tolerance,
+            ignore_errors,
+        ))
+        .await
+    }
+
+    pub async fn z_index_at_least(&self, n: i64) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_z_index_at_least(n, ignore_errors)).await
+    }
+
+    pub async fn has_computed_role<N>(&self, role: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_computed_role(role, ignore_errors)).await
+    }
+
+    pub async fn has_computed_label<N>(&self, label: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_has_computed_label(label, ignore_errors)).await
+    }
+
+    pub async fn accessible(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_accessible(ignore_errors)).await
+    }
+
+    pub async fn not_accessible(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_not_accessible(ignore_errors)).await
+    }
+
+    pub async fn becomes_inaccessible(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_becomes_inaccessible(ignore_errors)).await
+    }
+
+    /// Wait until the element has received exactly `n` events of the specified type since
+    /// counting started. See `conditions::element_event_count()` for when counting starts.
+    pub async fn event_count(&self, event: &str, n: usize) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_event_count(event, n, ignore_errors)).await
+    }
+
+    /// Wait until the selection of a text input or textarea is the specified range. Use the
+    /// same value for `start` and `end` to wait for the caret position.
+    /// See `conditions::element_selection_is()` for details.
+    pub async fn selection_is(&self, start: usize, end: usize) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_selection_is(start, end, ignore_errors)).await
+    }
+
+    pub async fn selected_option_text_is<N>(&self, text: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::select_has_selected_text(text, ignore_errors)).await
+    }
+
+    pub async fn has_option<N>(&self, text: N) -> WebDriverResult<()>
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::select_has_option(text, ignore_errors)).await
+    }
+
+    pub async fn screenshot_stable(&self, polls: usize) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_screenshot_stable(polls, ignore_errors)).await
+    }
+
+    /// Wait until the element's CSS animations have stopped, or the element has been removed.
+    /// See `conditions::element_animation_stopped()` for which animations are detected.
+    pub async fn animation_stopped(&self) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_animation_stopped(ignore_errors)).await
+    }
+
+    /// Wait until the element's text has been unchanged for the specified number of
+    /// consecutive polls. See `conditions::element_text_stable()`.
+    pub async fn text_stable(&self, polls: usize) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_text_stable(polls, ignore_errors)).await
+    }
+
+    pub async fn fully_within(&self, container: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_fully_within(container, ignore_errors)).await
+    }
+
+    pub async fn value_equals_of(&self, other: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_value_equals_element(other, ignore_errors)).await
+    }
+
+    pub async fn width_fraction_of(
+        &self,
+        parent: &WebElement<'_>,
+        fraction: f64,
+        tolerance: f64,
+    ) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_width_fraction_of(
+            parent,
+            fraction,
+            tolerance,
+            ignore_errors,
+        ))
+        .await
+    }
+
+    /// Wait until the carousel is scrolled to the slide at the specified 0-based index. See
+    /// `conditions::carousel_active_index()` for how the active slide is determined.
+    pub async fn carousel_at<B>(
+        &self,
+        index: usize,
+        slide_selector: B,
+        tolerance: f64,
+    ) -> WebDriverResult<()>
+    where
+        B: Into<SelectorBy<'static>>,
+    {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::carousel_active_index(
+            index,
+            slide_selector,
+            tolerance,
+            ignore_errors,
+        ))
+        .await
+    }
+
+    /// Wait until a screenshot of the element matches the specified baseline PNG image, with
+    /// at most `max_diff` of the pixels differing. This requires the `visual` feature. See
+    /// `conditions::element_matches_baseline()` for details, including the diff image that is
+    /// saved if it does not match.
+    #[cfg(feature = "visual")]
+    pub async fn matches_baseline(
+        &self,
+        baseline_png: &Path,
+        max_diff: f64,
+    ) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_matches_baseline(baseline_png, max_diff, ignore_errors))
+            .await
+    }
+
+    pub async fn child_scrolled_into_view(&self, child: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_child_scrolled_into_view(child, ignore_errors)).await
+    }
+
+    pub async fn above(&self, other: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_above(other, ignore_errors)).await
+    }
+
+    pub async fn below(&self, other: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_below(other, ignore_errors)).await
+    }
+
+    pub async fn left_of(&self, other: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_left_of(other, ignore_errors)).await
+    }
+
+    pub async fn right_of(&self, other: &WebElement<'_>) -> WebDriverResult<()> {
+        let ignore_errors = self.ignore_errors;
+        self.condition(conditions::element_is_right_of(other, ignore_errors)).await
+    }
+}
+
+/// A chain of conditions that will all be waited for together. See `ElementWaiter::and()`.
+pub struct ElementWaiterChain<'a> {
+    waiter: ElementWaiter<'a>,
+    conditions: Vec<ElementPredicate>,
+    /// The snapshot equivalents of the conditions, or None if any condition has none.
+    snapshot_checks: Option<Vec<SnapshotCheck>>,
+}
+
+impl<'a> ElementWaiterChain<'a> {
+    /// This does nothing, but allows conditions to be chained in a more readable way.
+    pub fn and(self) -> Self {
+        self
+    }
+
+    /// Add the specified condition to the chain.
+    pub fn condition(mut self, f: ElementPredicate) -> Self {
+        self.conditions.push(f);
+        self.snapshot_checks = None;
+        self
+    }
+
+    /// Add the specified built-in condition to the chain, together with its equivalent check
+    /// against a snapshot. See `ElementWaiter::consistent_snapshot()`.
+    fn builtin(mut self, f: ElementPredicate, check: SnapshotCheck) -> Self {
+        self.conditions.push(f);
+        if let Some(checks) = &mut self.snapshot_checks {
+            checks.push(check);
+        }
+        self
+    }
+
+    /// Wait until all of the conditions in the chain are met at the same time.
+    ///
+    /// If `ElementWaiter::consistent_snapshot()` is enabled and all conditions are built-in
+    /// conditions, they are evaluated against a single snapshot of the element per poll.
+    pub async fn all(self) -> WebDriverResult<()> {
+        match self.snapshot_checks {
+            Some(checks) if self.waiter.consistent_snapshot && !checks.is_empty() => {
+                let ignore_errors = self.waiter.ignore_errors;
+                let f = move |s: &ElementStateSnapshot| checks.iter().all(|check| check(s));
+                self.waiter
+                    .condition(conditions::element_snapshot_matches(f, true, ignore_errors))
+                    .await
+            }
+            _ => self.waiter.conditions(self.conditions).await,
+        }
+    }
+
+    pub fn displayed(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_displayed(ignore_errors),
+            snapshot_state(|s| s.displayed == Some(true)),
+        )
+    }
+
+    pub fn not_displayed(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_not_displayed(ignore_errors),
+            snapshot_state(|s| s.displayed == Some(false)),
+        )
+    }
+
+    pub fn selected(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_selected(ignore_errors),
+            snapshot_state(|s| s.selected == Some(true)),
+        )
+    }
+
+    pub fn not_selected(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_not_selected(ignore_errors),
+            snapshot_state(|s| s.selected == Some(false)),
+        )
+    }
+
+    pub fn enabled(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_enabled(ignore_errors),
+            snapshot_state(|s| s.enabled == Some(true)),
+        )
+    }
+
+    pub fn not_enabled(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_not_enabled(ignore_errors),
+            snapshot_state(|s| s.enabled == Some(false)),
+        )
+    }
+
+    pub fn clickable(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_clickable(ignore_errors),
+            snapshot_state(is_clickable_snapshot),
+        )
+    }
+
+    pub fn not_clickable(self) -> Self {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.builtin(
+            conditions::element_is_not_clickable(ignore_errors),
+            snapshot_state(|s| !is_clickable_snapshot(s)),
+        )
+    }
+
+    pub fn has_class<N>(self, class_name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        let check = snapshot_attribute("class", class_name.clone(), true);
+        self.builtin(conditions::element_has_class(class_name, ignore_errors), check)
+    }
+
+    pub fn lacks_class<N>(self, class_name: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        let check = snapshot_attribute("class", class_name.clone(), false);
+        self.builtin(conditions::element_lacks_class(class_name, ignore_errors), check)
+    }
+
+    pub fn has_text<N>(self, text: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        let check = snapshot_text(text.clone(), true);
+        self.builtin(conditions::element_has_text(text, ignore_errors), check)
+    }
+
+    pub fn lacks_text<N>(self, text: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        let check = snapshot_text(text.clone(), false);
+        self.builtin(conditions::element_lacks_text(text, ignore_errors), check)
+    }
+
+    pub fn has_value<N>(self, value: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.condition(conditions::element_has_value(value, ignore_errors))
+    }
+
+    pub fn lacks_value<N>(self, value: N) -> Self
+    where
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        self.condition(conditions::element_lacks_value(value, ignore_errors))
+    }
+
+    pub fn has_attribute<S, N>(self, attribute_name: S, value: N) -> Self
+    where
+        S: Into<String>,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        let attribute_name: String = attribute_name.into();
+        let check = snapshot_attribute(&attribute_name, value.clone(), true);
+        self.builtin(conditions::element_has_attribute(attribute_name, value, ignore_errors), check)
+    }
+
+    pub fn lacks_attribute<S, N>(self, attribute_name: S, value: N) -> Self
+    where
+        S: Into<String>,
+        N: Needle + Clone + Send + Sync + 'static,
+    {
+        let ignore_errors = self.waiter.ignore_errors;
+        let attribute_name: String = attribute_name.into();
+        let check = snapshot_attribute(&attribute_name, value.clone(), false);
+        self.builtin(
+            conditions::element_lacks_attribute(attribute_name, value, ignore_errors),
+            check,
+        )
+    }
 }
 
 /// Trait for enabling the ElementWaiter interface.
@@ -339,12 +2191,26 @@ pub trait ElementWaitable {
 impl ElementWaitable for WebElement<'_> {
     /// Return an ElementQuery instance for more executing powerful element queries.
     fn wait_until(&self) -> ElementWaiter {
-        let poller: ElementPoller =
-            self.session.config().get("ElementPoller").unwrap_or(ElementPoller::NoWait);
+        let poller = default_poller(self.session);
         ElementWaiter::new(&self, poller)
     }
 }
 
+/// Create an ElementWaiter for the specified element, with the specified error message to be
+/// returned in the case of timeout (see `ElementWaiter::error()`). An empty message uses the
+/// default.
+///
+/// This is an alternative to `ElementWaitable::wait_until()` that does not require the trait
+/// to be in scope:
+///
+/// ```ignore
+/// thirtyfour_query::wait(&elem, "Timed out waiting for the button").clickable().await?;
+/// ```
+pub fn wait<'a>(element: &'a WebElement<'a>, message: &str) -> ElementWaiter<'a> {
+    let poller = default_poller(element.session);
+    ElementWaiter::new(element, poller).error(message)
+}
+
 #[cfg(test)]
 /// This function checks if the public async methods implement Send. It is not intended to be executed.
 async fn _test_is_send() -> WebDriverResult<()> {
@@ -358,9 +2224,25 @@ async fn _test_is_send() -> WebDriverResult<()> {
     let caps = DesiredCapabilities::chrome();
     let driver = WebDriver::new("http://localhost:4444", &caps).await?;
     let elem = driver.find_element(By::Css(r#"div"#)).await?;
+    is_send_val(&wait(&elem, "").displayed());
+    is_send_val(&elem.wait_until().snapshot());
+    is_send_val(&elem.wait_until().consistent_snapshot(true).snapshot());
+    is_send_val(&elem.wait_until().until_snapshot(|s| s.present));
+    is_send_val(&elem.wait_until().on_poll(|_, _| {}).displayed());
+    is_send_val(&elem.wait_until().try_condition(conditions::element_is_displayed(true)));
+    is_send_val(
+        &elem
+            .wait_until()
+            .or_fail_if(conditions::element_has_class("error", true), "Failed")
+            .displayed(),
+    );
 
     // ElementWaitCondition
     is_send_val(&elem.wait_until().stale());
+    is_send_val(&elem.wait_until().scroll_into_view(true).clickable());
+    is_send_val(&elem.wait_until().no_descendants(By::Css(".child")));
+    is_send_val(&elem.wait_until().screenshot_on_success("screenshots").displayed());
+    is_send_val(&elem.wait_until().number_reaches(100.0, true));
     is_send_val(&elem.wait_until().displayed());
     is_send_val(&elem.wait_until().selected());
     is_send_val(&elem.wait_until().enabled());
@@ -368,5 +2250,72 @@ async fn _test_is_send() -> WebDriverResult<()> {
         Box::pin(async move { elem.is_enabled().await.or(Ok(false)) })
     })));
 
+    async fn is_enabled(elem: &WebElement<'_>) -> WebDriverResult<bool> {
+        elem.is_enabled().await
+    }
+    is_send_val(&elem.wait_until().condition(conditions::predicate(is_enabled)));
+    is_send_val(&elem.wait_until().until(is_enabled));
+    is_send_val(&elem.wait_until().until_sync(|_| true));
+
+    async fn text_value(elem: &WebElement<'_>) -> WebDriverResult<Option<String>> {
+        Ok(Some(elem.text().await?).filter(|x| !x.is_empty()))
+    }
+    is_send_val(&elem.wait_until().wait_for_value(text_value));
+
+    is_send_val(&elem.wait_until().outcome(
+        conditions::element_has_text("Saved", true),
+        conditions::element_has_class("error", true),
+    ));
+    is_send_val(&elem.wait_until().observe_transition(
+        conditions::element_is_not_enabled(true),
+        conditions::element_is_enabled(true),
+    ));
+    is_send_val(&elem.wait_until().check(conditions::element_is_displayed(true)));
+    is_send_val(
+        &elem.wait_until().condition_until(conditions::element_is_displayed(true), Instant::now()),
+    );
+
+    is_send_val(&elem.wait_until().and().displayed().and().enabled().all());
+    is_send_val(
+        &elem.wait_until().consistent_snapshot(true).and().displayed().has_text("Done").all(),
+    );
+
+    // Reusing a single ElementWaiter for multiple conditions.
+    let waiter = elem.wait_until().ignore_errors(false);
+    is_send_val(&waiter.displayed());
+    is_send_val(&waiter.not_displayed());
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_checks() {
+        let mut attributes = BTreeMap::new();
+        attributes.insert(String::from("class"), String::from("button primary"));
+        let snapshot = ElementStateSnapshot {
+            present: true,
+            displayed: Some(true),
+            enabled: Some(false),
+            selected: Some(false),
+            text: Some(String::from("Saving...")),
+            rect: None,
+            attributes: Some(attributes),
+        };
+
+        assert!(!is_clickable_snapshot(&snapshot));
+        assert!(snapshot_text("Saving...", true)(&snapshot));
+        assert!(!snapshot_text("Saving...", false)(&snapshot));
+        assert!(snapshot_attribute("class", StringMatch::new("primary").word(), true)(&snapshot));
+        assert!(snapshot_attribute("id", "submit", false)(&snapshot));
+        assert!(!snapshot_attribute("id", "submit", true)(&snapshot));
+
+        // Nothing matches an element that is not present, not even the negated checks.
+        let missing = ElementStateSnapshot::missing();
+        assert!(!snapshot_state(|s| s.displayed != Some(true))(&missing));
+        assert!(!snapshot_attribute("id", "submit", false)(&missing));
+    }
+}